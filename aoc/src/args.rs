@@ -0,0 +1,280 @@
+use aoc_common::{Error, Part, Result};
+
+pub const USAGE: &str = "usage: aoc run --day N [--part P] --input PATH|- [--json]
+       aoc verify [--day N]... [--input PATH]... [--answers PATH] [--record]
+       aoc profile [--day N]... [--input PATH] [--runs R]
+every command accepts --log FILTERS, e.g. --log debug or --log day13=debug,day15=trace";
+
+/// Default file of known answers, relative to the workspace directory
+pub const ANSWERS: &str = "answers.toml";
+
+/// Default number of times each phase is run by the `profile` command
+pub const RUNS: usize = 10;
+
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Run(RunArgs),
+    Verify(VerifyArgs),
+    Profile(ProfileArgs),
+}
+
+/// Arguments of the `run` command
+#[derive(Debug, PartialEq)]
+pub struct RunArgs {
+    pub day: u32,
+    pub parts: Vec<Part>,
+    pub input: String,
+    /// Print the answers as JSON records
+    pub json: bool,
+}
+
+/// Arguments of the `verify` command
+#[derive(Debug, PartialEq)]
+pub struct VerifyArgs {
+    /// Days to verify, all of them when empty
+    pub days: Vec<u32>,
+    /// Inputs to verify, the puzzle input of each day when empty
+    pub inputs: Vec<String>,
+    pub answers: String,
+    /// Store the answers of inputs without a recorded answer
+    pub record: bool,
+}
+
+/// Arguments of the `profile` command
+#[derive(Debug, PartialEq)]
+pub struct ProfileArgs {
+    /// Days to profile, all of them when empty
+    pub days: Vec<u32>,
+    /// Input to profile, the puzzle input of each day when not given
+    pub input: Option<String>,
+    /// Times each phase is run
+    pub runs: usize,
+}
+
+fn invalid(msg: String) -> Error {
+    Error::Usage(msg)
+}
+
+fn parse_day(value: &str) -> Result<u32> {
+    value
+        .parse::<u32>()
+        .map_err(|_| invalid(format!("invalid day {}", value)))
+}
+
+/// Returns the value following `flag`
+fn value<I: Iterator<Item = String>>(args: &mut I, flag: &str) -> Result<String> {
+    args.next()
+        .ok_or_else(|| invalid(format!("missing value for {}", flag)))
+}
+
+fn parse_run<I: Iterator<Item = String>>(mut args: I) -> Result<RunArgs> {
+    let mut day = None;
+    let mut parts = Part::BOTH.to_vec();
+    let mut input = None;
+    let mut json = false;
+
+    while let Some(flag) = args.next() {
+        match flag.as_str() {
+            "--day" => day = Some(parse_day(&value(&mut args, &flag)?)?),
+            "--part" => parts = vec![value(&mut args, &flag)?.parse()?],
+            "--input" => input = Some(value(&mut args, &flag)?),
+            "--json" => json = true,
+            _ => return Err(invalid(format!("unknown option {}", flag))),
+        }
+    }
+
+    match (day, input) {
+        (Some(day), Some(input)) => Ok(RunArgs {
+            day,
+            parts,
+            input,
+            json,
+        }),
+        (None, _) => Err(invalid(String::from("missing --day"))),
+        (_, None) => Err(invalid(String::from("missing --input"))),
+    }
+}
+
+fn parse_verify<I: Iterator<Item = String>>(mut args: I) -> Result<VerifyArgs> {
+    let mut verify = VerifyArgs {
+        days: vec![],
+        inputs: vec![],
+        answers: String::from(ANSWERS),
+        record: false,
+    };
+
+    while let Some(flag) = args.next() {
+        match flag.as_str() {
+            "--day" => verify.days.push(parse_day(&value(&mut args, &flag)?)?),
+            "--input" => verify.inputs.push(value(&mut args, &flag)?),
+            "--answers" => verify.answers = value(&mut args, &flag)?,
+            "--record" => verify.record = true,
+            _ => return Err(invalid(format!("unknown option {}", flag))),
+        }
+    }
+
+    if !verify.inputs.is_empty() && verify.days.len() != 1 {
+        return Err(invalid(String::from("--input needs exactly one --day")));
+    }
+    Ok(verify)
+}
+
+fn parse_profile<I: Iterator<Item = String>>(mut args: I) -> Result<ProfileArgs> {
+    let mut profile = ProfileArgs {
+        days: vec![],
+        input: None,
+        runs: RUNS,
+    };
+
+    while let Some(flag) = args.next() {
+        match flag.as_str() {
+            "--day" => profile.days.push(parse_day(&value(&mut args, &flag)?)?),
+            "--input" => profile.input = Some(value(&mut args, &flag)?),
+            "--runs" => {
+                let runs = value(&mut args, &flag)?;
+                profile.runs = match runs.parse::<usize>() {
+                    Ok(runs) if runs > 0 => runs,
+                    _ => return Err(invalid(format!("invalid number of runs {}", runs))),
+                };
+            }
+            _ => return Err(invalid(format!("unknown option {}", flag))),
+        }
+    }
+
+    if profile.input.is_some() && profile.days.len() != 1 {
+        return Err(invalid(String::from("--input needs exactly one --day")));
+    }
+    Ok(profile)
+}
+
+/// Removes `--log FILTERS` from the arguments of any command, returning the filters
+pub fn split_log<I: Iterator<Item = String>>(mut args: I) -> Result<(Option<String>, Vec<String>)> {
+    let mut log = None;
+    let mut rest = vec![];
+    while let Some(arg) = args.next() {
+        if arg == "--log" {
+            log = Some(value(&mut args, &arg)?);
+        } else {
+            rest.push(arg);
+        }
+    }
+    Ok((log, rest))
+}
+
+/// Parses the arguments following the program name
+pub fn parse_args<I: Iterator<Item = String>>(mut args: I) -> Result<Command> {
+    match args.next().as_deref() {
+        Some("run") => Ok(Command::Run(parse_run(args)?)),
+        Some("verify") => Ok(Command::Verify(parse_verify(args)?)),
+        Some("profile") => Ok(Command::Profile(parse_profile(args)?)),
+        Some(cmd) => Err(invalid(format!("unknown command {}", cmd))),
+        None => Err(invalid(String::from("missing command"))),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn args(line: &str) -> impl Iterator<Item = String> + '_ {
+        line.split_whitespace().map(String::from)
+    }
+
+    #[test]
+    fn test_parse_run() {
+        assert_eq!(
+            Command::Run(RunArgs {
+                day: 15,
+                parts: vec![Part::Two],
+                input: String::from("input15.txt"),
+                json: false,
+            }),
+            parse_args(args("run --day 15 --part 2 --input input15.txt")).unwrap()
+        );
+
+        match parse_args(args("run --input in.txt --day 1")).unwrap() {
+            Command::Run(run_args) => assert_eq!(Part::BOTH.to_vec(), run_args.parts),
+            _ => panic!("expected the run command"),
+        }
+
+        match parse_args(args("run --json --input - --day 1")).unwrap() {
+            Command::Run(run_args) => assert!(run_args.json),
+            _ => panic!("expected the run command"),
+        }
+
+        assert!(parse_args(args("run --day 1")).is_err());
+        assert!(parse_args(args("run --input in.txt")).is_err());
+        assert!(parse_args(args("run --day x --input in.txt")).is_err());
+        assert!(parse_args(args("run --day 1 --part 3 --input in.txt")).is_err());
+        assert!(parse_args(args("run --day 1 --input")).is_err());
+        assert!(parse_args(args("solve --day 1 --input in.txt")).is_err());
+    }
+
+    #[test]
+    fn test_parse_verify() {
+        assert_eq!(
+            Command::Verify(VerifyArgs {
+                days: vec![],
+                inputs: vec![],
+                answers: String::from(ANSWERS),
+                record: false,
+            }),
+            parse_args(args("verify")).unwrap()
+        );
+
+        assert_eq!(
+            Command::Verify(VerifyArgs {
+                days: vec![3],
+                inputs: vec![String::from("a.txt"), String::from("b.txt")],
+                answers: String::from("mine.toml"),
+                record: true,
+            }),
+            parse_args(args(
+                "verify --day 3 --input a.txt --input b.txt --answers mine.toml --record"
+            ))
+            .unwrap()
+        );
+
+        assert!(parse_args(args("verify --input a.txt")).is_err());
+        assert!(parse_args(args("verify --day 1 --day 2 --input a.txt")).is_err());
+        assert!(parse_args(args("verify --part 1")).is_err());
+    }
+
+    #[test]
+    fn test_parse_profile() {
+        assert_eq!(
+            Command::Profile(ProfileArgs {
+                days: vec![],
+                input: None,
+                runs: RUNS,
+            }),
+            parse_args(args("profile")).unwrap()
+        );
+
+        assert_eq!(
+            Command::Profile(ProfileArgs {
+                days: vec![15],
+                input: Some(String::from("-")),
+                runs: 3,
+            }),
+            parse_args(args("profile --day 15 --runs 3 --input -")).unwrap()
+        );
+
+        assert!(parse_args(args("profile --runs 0")).is_err());
+        assert!(parse_args(args("profile --runs x")).is_err());
+        assert!(parse_args(args("profile --input a.txt")).is_err());
+    }
+
+    #[test]
+    fn test_split_log() {
+        let (log, rest) = split_log(args("run --day 13 --log day13=debug --input -")).unwrap();
+        assert_eq!(Some(String::from("day13=debug")), log);
+        assert_eq!(args("run --day 13 --input -").collect::<Vec<_>>(), rest);
+
+        let (log, rest) = split_log(args("verify")).unwrap();
+        assert_eq!(None, log);
+        assert_eq!(vec![String::from("verify")], rest);
+
+        assert!(split_log(args("verify --log")).is_err());
+    }
+}
@@ -0,0 +1,117 @@
+use aoc_common::Solve;
+
+/// A solved day of the calendar
+pub struct Day {
+    pub number: u32,
+    pub solver: &'static (dyn Solve + Sync),
+    /// Puzzle input of the day, relative to the workspace directory
+    pub input: &'static str,
+}
+
+pub static DAYS: [Day; 17] = [
+    Day {
+        number: 1,
+        solver: &day01::Day01,
+        input: "day01/input_01.txt",
+    },
+    Day {
+        number: 2,
+        solver: &day02::Day02,
+        input: "day02/input02.txt",
+    },
+    Day {
+        number: 3,
+        solver: &day03::Day03,
+        input: "day03/input03.txt",
+    },
+    Day {
+        number: 4,
+        solver: &day04::Day04,
+        input: "day04/input04.txt",
+    },
+    Day {
+        number: 5,
+        solver: &day05::Day05,
+        input: "day05/input05.txt",
+    },
+    Day {
+        number: 6,
+        solver: &day06::Day06,
+        input: "day06/input06.txt",
+    },
+    Day {
+        number: 7,
+        solver: &day07::Day07,
+        input: "day07/input07.txt",
+    },
+    Day {
+        number: 8,
+        solver: &day08::Day08,
+        input: "day08/input08.txt",
+    },
+    Day {
+        number: 9,
+        solver: &day09::Day09,
+        input: "day09/input09.txt",
+    },
+    Day {
+        number: 10,
+        solver: &day10::Day10,
+        input: "day10/input10.txt",
+    },
+    Day {
+        number: 11,
+        solver: &day11::Day11,
+        input: "day11/input11.txt",
+    },
+    Day {
+        number: 12,
+        solver: &day12::Day12,
+        input: "day12/input12.txt",
+    },
+    Day {
+        number: 13,
+        solver: &day13::Day13,
+        input: "day13/input13.txt",
+    },
+    Day {
+        number: 14,
+        solver: &day14::Day14,
+        input: "day14/input14.txt",
+    },
+    Day {
+        number: 15,
+        solver: &day15::Day15,
+        input: "day15/input15.txt",
+    },
+    Day {
+        number: 16,
+        solver: &day16::Day16,
+        input: "day16/input16.txt",
+    },
+    Day {
+        number: 17,
+        solver: &day17::Day17,
+        input: "day17/input17.txt",
+    },
+];
+
+pub fn find(number: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_find() {
+        for number in 1..=17 {
+            let day = find(number).unwrap();
+            assert_eq!(number, day.number);
+            assert_eq!(number, day.solver.day());
+        }
+        assert!(find(0).is_none());
+        assert!(find(18).is_none());
+    }
+}
@@ -0,0 +1,173 @@
+//! Runs the solution of any day
+//!
+//! `aoc run --day 15 [--part 2] --input input15.txt` prints the answers, both parts are
+//! solved when no part is given. The input is read from stdin when its path is `-`.
+//! With `--json` each answer is printed as a line of JSON with the day, part, answer,
+//! elapsed time and input identifier.
+//!
+//! `aoc verify` solves every day with its puzzle input and compares the answers with
+//! the ones recorded in `answers.toml`, the days and inputs can be chosen with `--day`
+//! and `--input`. With `--record` the answers of unknown inputs are added to the file.
+//!
+//! `aoc profile` runs the parsing and both parts of every day `--runs` times and prints
+//! a table with the minimum, median and maximum times and the peak of allocated memory.
+//!
+//! `--log FILTERS` shows the log of the solvers on stderr, e.g. `--log day15=debug`.
+
+mod args;
+mod days;
+
+use aoc_common::{
+    init_logging, input_id, print_answer, profile, read_input, Answers, CountingAlloc, Error, Part,
+    Record, Result, Solution, Verdict,
+};
+use args::{Command, ProfileArgs, RunArgs, VerifyArgs, USAGE};
+use std::env;
+use std::process;
+
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+fn find_day(number: u32) -> Result<&'static days::Day> {
+    days::find(number).ok_or_else(|| Error::Usage(format!("no solution for day {}", number)))
+}
+
+fn run(args: &RunArgs) -> Result<()> {
+    let day = find_day(args.day)?;
+
+    let input = read_input(&args.input)?;
+    let id = input_id(&input);
+    for solution in day.solver.solve(&mut input.as_slice(), &args.parts)? {
+        if args.json {
+            println!("{}", Record::new(day.number, &id, &solution).to_json());
+        } else {
+            let label = format!("Day {:02} part {}", day.number, solution.part);
+            print_answer(&label, &solution.answer);
+        }
+    }
+    Ok(())
+}
+
+/// Days with the given numbers, all of them when there are none
+fn select_days(numbers: &[u32]) -> Result<Vec<&'static days::Day>> {
+    if numbers.is_empty() {
+        Ok(days::DAYS.iter().collect())
+    } else {
+        numbers.iter().map(|n| find_day(*n)).collect()
+    }
+}
+
+/// Number of answers of each verdict
+#[derive(Debug, Default)]
+struct Summary {
+    passed: usize,
+    failed: usize,
+    unknown: usize,
+}
+
+/// Verifies the answers of one input, recording them in `answers` if `record` is set
+fn verify_input(
+    day: &days::Day,
+    path: &str,
+    answers: &mut Answers,
+    record: bool,
+    summary: &mut Summary,
+) -> Result<()> {
+    let input = read_input(path)?;
+    let id = input_id(&input);
+
+    for Solution { part, answer, .. } in day.solver.solve(&mut input.as_slice(), &Part::BOTH)? {
+        let label = format!("Day {:02} part {} [{}]", day.number, part, path);
+        match answers.check(day.number, part, &id, &answer) {
+            Verdict::Pass => {
+                summary.passed += 1;
+                println!("{}: pass", label);
+            }
+            Verdict::Fail { expected } => {
+                summary.failed += 1;
+                println!(
+                    "{}: FAIL, expected {:?} got {:?}",
+                    label,
+                    expected,
+                    answer.to_string()
+                );
+            }
+            Verdict::Unknown => {
+                summary.unknown += 1;
+                println!(
+                    "{}: unknown, got {:?} for input {}",
+                    label,
+                    answer.to_string(),
+                    id
+                );
+                if record {
+                    answers.insert(day.number, part, &id, &answer);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns true if none of the answers failed
+fn verify(args: &VerifyArgs) -> Result<bool> {
+    let mut answers = Answers::load(&args.answers)?;
+    let days = select_days(&args.days)?;
+
+    let mut summary = Summary::default();
+    for day in days {
+        if args.inputs.is_empty() {
+            verify_input(day, day.input, &mut answers, args.record, &mut summary)?;
+        }
+        for path in &args.inputs {
+            verify_input(day, path, &mut answers, args.record, &mut summary)?;
+        }
+    }
+
+    println!(
+        "{} passed, {} failed, {} unknown",
+        summary.passed, summary.failed, summary.unknown
+    );
+    if args.record && summary.unknown > 0 {
+        answers.save(&args.answers)?;
+        println!("recorded {} answers in {}", summary.unknown, args.answers);
+    }
+    Ok(summary.failed == 0)
+}
+
+fn profile(args: &ProfileArgs) -> Result<()> {
+    let days = select_days(&args.days)?;
+
+    let mut profiles = vec![];
+    for day in days {
+        let input = read_input(args.input.as_deref().unwrap_or(day.input))?;
+        profiles.push(day.solver.profile(&input, &Part::BOTH, args.runs)?);
+    }
+    println!("{}", profile::table(&profiles));
+    Ok(())
+}
+
+fn main() {
+    let result = args::split_log(env::args().skip(1))
+        .and_then(|(log, args)| {
+            init_logging(log.as_deref());
+            args::parse_args(args.into_iter())
+        })
+        .and_then(|command| match command {
+            Command::Run(args) => run(&args).map(|_| true),
+            Command::Verify(args) => verify(&args),
+            Command::Profile(args) => profile(&args).map(|_| true),
+        });
+
+    match result {
+        Ok(true) => (),
+        Ok(false) => process::exit(1),
+        Err(why) => {
+            eprintln!("Error: {}", why);
+            if let Error::Usage(_) = why {
+                eprintln!("{}", USAGE);
+            }
+            process::exit(1);
+        }
+    }
+}
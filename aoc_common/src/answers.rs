@@ -0,0 +1,171 @@
+use crate::error::{Error, Result};
+use crate::solver::{Answer, Part};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+
+/// Identifies an input by the FNV-1a hash of its contents, as 16 hex digits
+pub fn input_id(input: &[u8]) -> String {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in input {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    format!("{:016x}", hash)
+}
+
+/// Result of comparing an answer with the registry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail {
+        expected: String,
+    },
+    /// There is no answer recorded for the input
+    Unknown,
+}
+
+/// Registry of known answers, stored as TOML with a table per day and input
+///
+/// ```toml
+/// [day01.8f916f0e8be8e646]
+/// part1 = "1228"
+/// part2 = "1257"
+/// ```
+#[derive(Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Answers {
+    #[serde(flatten)]
+    days: BTreeMap<String, BTreeMap<String, BTreeMap<String, String>>>,
+}
+
+fn day_key(day: u32) -> String {
+    format!("day{:02}", day)
+}
+
+fn part_key(part: Part) -> String {
+    format!("part{}", part)
+}
+
+impl Answers {
+    pub fn new() -> Answers {
+        Answers::default()
+    }
+
+    pub fn parse(text: &str) -> Result<Answers> {
+        toml::from_str(text).map_err(|why| Error::Answers(why.to_string()))
+    }
+
+    /// Loads the registry from `path`, a missing file is an empty registry
+    pub fn load(path: &str) -> Result<Answers> {
+        match fs::read_to_string(path) {
+            Ok(text) => Answers::parse(&text),
+            Err(why) if why.kind() == io::ErrorKind::NotFound => Ok(Answers::new()),
+            Err(why) => Err(Error::OpenInput {
+                path: String::from(path),
+                why,
+            }),
+        }
+    }
+
+    pub fn to_toml(&self) -> Result<String> {
+        toml::to_string(self).map_err(|why| Error::Answers(why.to_string()))
+    }
+
+    pub fn save(&self, path: &str) -> Result<()> {
+        Ok(fs::write(path, self.to_toml()?)?)
+    }
+
+    pub fn get(&self, day: u32, part: Part, input: &str) -> Option<&str> {
+        self.days
+            .get(&day_key(day))?
+            .get(input)?
+            .get(&part_key(part))
+            .map(|answer| answer.as_str())
+    }
+
+    /// Records the answer of a part for the input, replacing any previous one
+    pub fn insert(&mut self, day: u32, part: Part, input: &str, answer: &Answer) {
+        self.days
+            .entry(day_key(day))
+            .or_default()
+            .entry(String::from(input))
+            .or_default()
+            .insert(part_key(part), answer.to_string());
+    }
+
+    pub fn check(&self, day: u32, part: Part, input: &str, answer: &Answer) -> Verdict {
+        match self.get(day, part, input) {
+            Some(expected) if expected == answer.to_string() => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: String::from(expected),
+            },
+            None => Verdict::Unknown,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_input_id() {
+        assert_eq!("cbf29ce484222325", input_id(b""));
+        assert_eq!("af63dc4c8601ec8c", input_id(b"a"));
+        assert_ne!(input_id(b"199\n200\n"), input_id(b"199\n201\n"));
+    }
+
+    #[test]
+    fn test_check() {
+        let answers = Answers::parse(
+            r#"
+            [day01.0123456789abcdef]
+            part1 = "7"
+            "#,
+        )
+        .unwrap();
+
+        let input = "0123456789abcdef";
+        assert_eq!(
+            Verdict::Pass,
+            answers.check(1, Part::One, input, &Answer::UInt(7))
+        );
+        assert_eq!(
+            Verdict::Fail {
+                expected: String::from("7")
+            },
+            answers.check(1, Part::One, input, &Answer::UInt(5))
+        );
+        assert_eq!(
+            Verdict::Unknown,
+            answers.check(1, Part::Two, input, &Answer::UInt(5))
+        );
+        assert_eq!(
+            Verdict::Unknown,
+            answers.check(2, Part::One, input, &Answer::UInt(7))
+        );
+    }
+
+    #[test]
+    fn test_insert_round_trip() {
+        let mut answers = Answers::new();
+        answers.insert(13, Part::One, "ff", &Answer::UInt(17));
+        answers.insert(
+            13,
+            Part::Two,
+            "ff",
+            &Answer::Text(String::from("#..#\n####")),
+        );
+
+        let text = answers.to_toml().unwrap();
+        let loaded = Answers::parse(&text).unwrap();
+        assert_eq!(answers, loaded);
+        assert_eq!(Some("#..#\n####"), loaded.get(13, Part::Two, "ff"));
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(Answers::parse("[day01.ff]\npart1 = 7").is_err());
+    }
+}
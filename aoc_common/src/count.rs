@@ -0,0 +1,133 @@
+use crate::error::{Error, Result};
+use crate::solver::Answer;
+use std::fmt::{Debug, Display};
+
+/// Number of things growing exponentially, its operations fail instead of wrapping around
+///
+/// Implemented by the fixed size unsigned integers, which overflow, and by `BigUint`
+/// with the `bigint` feature, which never does.
+pub trait Count: Clone + Ord + Debug + Display {
+    fn zero() -> Self;
+    fn from_u64(val: u64) -> Self;
+    fn try_add(&self, other: &Self) -> Option<Self>;
+    fn try_sub(&self, other: &Self) -> Option<Self>;
+    fn try_mul(&self, other: &Self) -> Option<Self>;
+    fn into_answer(self) -> Answer;
+}
+
+macro_rules! count_int {
+    ($($t:ty),*) => {
+        $(
+            impl Count for $t {
+                fn zero() -> Self {
+                    0
+                }
+
+                fn from_u64(val: u64) -> Self {
+                    val as $t
+                }
+
+                fn try_add(&self, other: &Self) -> Option<Self> {
+                    self.checked_add(*other)
+                }
+
+                fn try_sub(&self, other: &Self) -> Option<Self> {
+                    self.checked_sub(*other)
+                }
+
+                fn try_mul(&self, other: &Self) -> Option<Self> {
+                    self.checked_mul(*other)
+                }
+
+                fn into_answer(self) -> Answer {
+                    self.into()
+                }
+            }
+        )*
+    };
+}
+
+count_int!(u64, usize);
+
+#[cfg(feature = "bigint")]
+impl Count for num_bigint::BigUint {
+    fn zero() -> Self {
+        Self::default()
+    }
+
+    fn from_u64(val: u64) -> Self {
+        val.into()
+    }
+
+    fn try_add(&self, other: &Self) -> Option<Self> {
+        Some(self + other)
+    }
+
+    fn try_sub(&self, other: &Self) -> Option<Self> {
+        if other > self {
+            None
+        } else {
+            Some(self - other)
+        }
+    }
+
+    fn try_mul(&self, other: &Self) -> Option<Self> {
+        Some(self * other)
+    }
+
+    fn into_answer(self) -> Answer {
+        Answer::Text(self.to_string())
+    }
+}
+
+/// Error of an operation on `what` that doesn't fit in the count type
+pub fn overflow(what: &str) -> Error {
+    Error::Overflow(String::from(what))
+}
+
+/// Adds `other` to `count` failing with an overflow of `what`
+pub fn add_to<C: Count>(count: &mut C, other: &C, what: &str) -> Result<()> {
+    *count = count.try_add(other).ok_or_else(|| overflow(what))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_add_to() {
+        let mut count = u64::MAX - 1;
+        add_to(&mut count, &1, "fish").unwrap();
+        assert_eq!(u64::MAX, count);
+
+        match add_to(&mut count, &1, "fish") {
+            Err(Error::Overflow(what)) => assert_eq!("fish", what),
+            _ => panic!("expected an overflow"),
+        }
+        assert_eq!(u64::MAX, count);
+    }
+
+    #[test]
+    fn test_try_ops() {
+        assert_eq!(Some(6), 2_usize.try_mul(&3));
+        assert_eq!(None, u64::MAX.try_mul(&2));
+        assert_eq!(None, 2_u64.try_sub(&3));
+        assert_eq!(Answer::UInt(5), 5_u64.into_answer());
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn test_biguint() {
+        use num_bigint::BigUint;
+
+        let max = BigUint::from_u64(u64::MAX);
+        let square = max.try_mul(&max).unwrap();
+        assert_eq!(
+            "340282366920938463426481119284349108225",
+            square.to_string()
+        );
+        assert_eq!(None, max.try_sub(&square));
+        assert_eq!(Answer::Text(square.to_string()), square.into_answer());
+    }
+}
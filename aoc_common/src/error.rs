@@ -0,0 +1,185 @@
+use crate::grid::ParseGridError;
+use std::fmt;
+use std::io;
+
+/// Place in the input where parsing failed, line and column start at 1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+    /// The offending token
+    pub token: String,
+}
+
+impl Location {
+    pub fn new(line: usize, column: usize, token: &str) -> Location {
+        Location {
+            line,
+            column,
+            token: String::from(token),
+        }
+    }
+
+    /// Location of `token` in the line number `line`, `token` must be a slice of `text`
+    pub fn in_line(line: usize, text: &str, token: &str) -> Location {
+        let offset = (token.as_ptr() as usize)
+            .checked_sub(text.as_ptr() as usize)
+            .filter(|offset| offset + token.len() <= text.len())
+            .unwrap_or(0);
+        Location::new(line, text[..offset].chars().count() + 1, token)
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    /// The input file given by the user could not be opened
+    OpenInput {
+        path: String,
+        why: io::Error,
+    },
+    Grid(ParseGridError),
+    /// Located parse error of the input of one of the days
+    Parse(Box<dyn std::error::Error + Send + Sync>),
+    /// The input could be read but does not have the expected format
+    InvalidInput(String),
+    /// The command line arguments are not valid, the binaries print their usage after it
+    Usage(String),
+    /// The solver could not find an answer for the input
+    NoSolution(String),
+    /// The file of known answers is malformed
+    Answers(String),
+    /// A count grew larger than its type can hold
+    Overflow(String),
+    /// The input is well formed but breaks a limit given by the user
+    Constraint(String),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(why) => write!(f, "error reading input: {}", why),
+            Error::OpenInput { path, why } => {
+                write!(f, "cannot open input file {}: {}", path, why)
+            }
+            Error::Grid(why) => write!(f, "{}", why),
+            Error::Parse(why) => write!(f, "{}", why),
+            Error::InvalidInput(why) => write!(f, "invalid input: {}", why),
+            Error::Usage(why) => write!(f, "invalid arguments: {}", why),
+            Error::NoSolution(why) => write!(f, "no solution: {}", why),
+            Error::Answers(why) => write!(f, "invalid answers file: {}", why),
+            Error::Overflow(what) => write!(f, "arithmetic overflow: {}", what),
+            Error::Constraint(why) => write!(f, "constraint violated: {}", why),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<io::Error> for Error {
+    fn from(why: io::Error) -> Self {
+        Error::Io(why)
+    }
+}
+
+impl From<ParseGridError> for Error {
+    fn from(why: ParseGridError) -> Self {
+        Error::Grid(why)
+    }
+}
+
+/// Declares the located parse error of a day
+///
+/// The enum gets an `Io` variant for the errors reading the input besides the given ones,
+/// whose messages are written by the arms of `fmt`. It converts from `io::Error`, and into
+/// `Error::Parse` so that `?` works in `Solver::parse`.
+///
+/// ```
+/// use aoc_common::Location;
+///
+/// aoc_common::parse_error! {
+///     /// Errors found parsing the depths
+///     pub enum ParseError {
+///         /// A line that is not a depth
+///         InvalidDepth(Location),
+///     }
+///
+///     fn fmt(&self, f) {
+///         ParseError::InvalidDepth(loc) => write!(f, "{}: invalid depth '{}'", loc, loc.token),
+///     }
+/// }
+///
+/// let why = ParseError::InvalidDepth(Location::new(2, 1, "x"));
+/// assert_eq!("2:1: invalid depth 'x'", why.to_string());
+/// let why = ParseError::from(std::io::Error::other("broken pipe"));
+/// assert_eq!("error reading input: broken pipe", why.to_string());
+/// assert!(matches!(aoc_common::Error::from(why), aoc_common::Error::Parse(_)));
+/// ```
+#[macro_export]
+macro_rules! parse_error {
+    (
+        $(#[$meta:meta])*
+        $vis:vis enum $name:ident {
+            $($variants:tt)*
+        }
+
+        fn fmt(&self, $f:ident) {
+            $($arms:tt)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug)]
+        $vis enum $name {
+            /// The input could not be read
+            Io(::std::io::Error),
+            $($variants)*
+        }
+
+        impl ::std::fmt::Display for $name {
+            fn fmt(&self, $f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                match self {
+                    $name::Io(why) => write!($f, "error reading input: {}", why),
+                    $($arms)*
+                }
+            }
+        }
+
+        impl ::std::error::Error for $name {}
+
+        impl ::std::convert::From<::std::io::Error> for $name {
+            fn from(why: ::std::io::Error) -> Self {
+                $name::Io(why)
+            }
+        }
+
+        impl ::std::convert::From<$name> for $crate::Error {
+            fn from(why: $name) -> Self {
+                $crate::Error::Parse(::std::boxed::Box::new(why))
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_location_in_line() {
+        let text = "forward x";
+        let token = text.split_whitespace().nth(1).unwrap();
+        assert_eq!(Location::new(3, 9, "x"), Location::in_line(3, text, token));
+        assert_eq!("3:9", Location::in_line(3, text, token).to_string());
+
+        // tokens outside the line are placed at the start of it
+        assert_eq!(Location::new(1, 1, "y"), Location::in_line(1, text, "y"));
+    }
+}
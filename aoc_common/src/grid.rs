@@ -0,0 +1,424 @@
+use std::fmt;
+use std::io::{self, BufRead};
+use std::iter::StepBy;
+use std::ops::{Index, IndexMut};
+use std::slice;
+
+/// Position inside a grid as (row, column)
+pub type Pos = (usize, usize);
+
+/// Offsets of the 4 connected neighbours: up, down, left, right
+const NEIGHBOURS_4: [(isize, isize); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
+
+/// Offsets of the 8 connected neighbours in row major order
+const NEIGHBOURS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (-1, 0),
+    (-1, 1),
+    (0, -1),
+    (0, 1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+];
+
+#[derive(Debug)]
+pub enum ParseGridError {
+    Io(io::Error),
+    /// A character that could not be converted into a cell, line and column start at 1
+    InvalidCell {
+        line: usize,
+        column: usize,
+        found: char,
+    },
+    /// A row with a different number of cells than the first one
+    RaggedRow {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl fmt::Display for ParseGridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseGridError::Io(why) => write!(f, "error reading grid: {}", why),
+            ParseGridError::InvalidCell {
+                line,
+                column,
+                found,
+            } => write!(f, "{}:{}: invalid grid cell '{}'", line, column, found),
+            ParseGridError::RaggedRow {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "{}:1: row has {} cells, expected {}",
+                line, found, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseGridError {}
+
+impl From<io::Error> for ParseGridError {
+    fn from(why: io::Error) -> Self {
+        ParseGridError::Io(why)
+    }
+}
+
+/// Dense 2D grid stored in row major order
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    rows: usize,
+    cols: usize,
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from cells in row major order, returns None if the sizes do not match
+    pub fn from_vec(rows: usize, cols: usize, cells: Vec<T>) -> Option<Grid<T>> {
+        if rows * cols != cells.len() {
+            return None;
+        }
+        Some(Grid { cells, rows, cols })
+    }
+
+    pub fn from_fn<F: FnMut(Pos) -> T>(rows: usize, cols: usize, mut f: F) -> Grid<T> {
+        let mut cells = Vec::with_capacity(rows * cols);
+        for i in 0..rows {
+            for j in 0..cols {
+                cells.push(f((i, j)));
+            }
+        }
+        Grid { cells, rows, cols }
+    }
+
+    /// Parses one row per line, every character is converted into a cell with `f`
+    pub fn parse<R, F>(reader: R, mut f: F) -> Result<Grid<T>, ParseGridError>
+    where
+        R: BufRead,
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut cells = vec![];
+        let mut rows = 0;
+        let mut cols = 0;
+
+        for (cnt, line_) in reader.lines().enumerate() {
+            let line = line_?;
+            let line = line.trim_end();
+            if line.is_empty() {
+                continue;
+            }
+
+            let mut found = 0;
+            for (col, c) in line.chars().enumerate() {
+                match f(c) {
+                    Some(val) => cells.push(val),
+                    None => {
+                        return Err(ParseGridError::InvalidCell {
+                            line: cnt + 1,
+                            column: col + 1,
+                            found: c,
+                        })
+                    }
+                }
+                found += 1;
+            }
+
+            if rows == 0 {
+                cols = found;
+            } else if found != cols {
+                return Err(ParseGridError::RaggedRow {
+                    line: cnt + 1,
+                    expected: cols,
+                    found,
+                });
+            }
+            rows += 1;
+        }
+
+        Ok(Grid { cells, rows, cols })
+    }
+
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, pos: Pos) -> bool {
+        pos.0 < self.rows && pos.1 < self.cols
+    }
+
+    /// Bounds checked access, returns None outside of the grid
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        if self.contains(pos) {
+            self.cells.get(pos.0 * self.cols + pos.1)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            self.cells.get_mut(pos.0 * self.cols + pos.1)
+        } else {
+            None
+        }
+    }
+
+    /// Position of the bottom right cell
+    pub fn last_pos(&self) -> Option<Pos> {
+        if self.is_empty() {
+            None
+        } else {
+            Some((self.rows - 1, self.cols - 1))
+        }
+    }
+
+    pub fn row(&self, i: usize) -> &[T] {
+        &self.cells[i * self.cols..(i + 1) * self.cols]
+    }
+
+    pub fn row_mut(&mut self, i: usize) -> &mut [T] {
+        &mut self.cells[i * self.cols..(i + 1) * self.cols]
+    }
+
+    pub fn column(&self, j: usize) -> StepBy<slice::Iter<'_, T>> {
+        assert!(j < self.cols, "column {} out of bounds", j);
+        self.cells[j..].iter().step_by(self.cols)
+    }
+
+    pub fn iter(&self) -> slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+
+    /// Iterates over all the positions in row major order
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let cols = self.cols;
+        (0..self.cells.len()).map(move |n| (n / cols, n % cols))
+    }
+
+    /// Iterates over the cells together with their positions
+    pub fn indexed_iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Horizontal and vertical neighbours inside the grid: up, down, left, right
+    pub fn neighbours4(&self, pos: Pos) -> impl Iterator<Item = Pos> {
+        self.offsets(pos, &NEIGHBOURS_4)
+    }
+
+    /// Horizontal, vertical and diagonal neighbours inside the grid in row major order
+    pub fn neighbours8(&self, pos: Pos) -> impl Iterator<Item = Pos> {
+        self.offsets(pos, &NEIGHBOURS_8)
+    }
+
+    fn offsets(&self, pos: Pos, deltas: &'static [(isize, isize)]) -> impl Iterator<Item = Pos> {
+        let (rows, cols) = (self.rows, self.cols);
+        deltas.iter().filter_map(move |(di, dj)| {
+            let i = pos.0.checked_add_signed(*di)?;
+            let j = pos.1.checked_add_signed(*dj)?;
+            if i < rows && j < cols {
+                Some((i, j))
+            } else {
+                None
+            }
+        })
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            rows: self.rows,
+            cols: self.cols,
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn from_elem(rows: usize, cols: usize, val: T) -> Grid<T> {
+        Grid {
+            cells: vec![val; rows * cols],
+            rows,
+            cols,
+        }
+    }
+}
+
+impl<T: From<u8>> Grid<T> {
+    /// Parses a grid of decimal digits, one row per line
+    pub fn parse_digits<R: BufRead>(reader: R) -> Result<Grid<T>, ParseGridError> {
+        Grid::parse(reader, |c| c.to_digit(10).map(|d| T::from(d as u8)))
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &T {
+        match self.get(pos) {
+            Some(val) => val,
+            None => panic!(
+                "position {:?} out of bounds for {}x{} grid",
+                pos, self.rows, self.cols
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut T {
+        let (rows, cols) = (self.rows, self.cols);
+        match self.get_mut(pos) {
+            Some(val) => val,
+            None => panic!(
+                "position {:?} out of bounds for {}x{} grid",
+                pos, rows, cols
+            ),
+        }
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for i in 0..self.rows {
+            for val in self.row(i) {
+                write!(f, "{}", val)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const F: &[u8] = b"2199943210\n3987894921\n9856789892\n8767896789\n9899965678" as &[u8];
+
+    #[test]
+    fn test_parse_digits() {
+        let grid: Grid<u8> = Grid::parse_digits(F).unwrap();
+
+        assert_eq!(5, grid.rows());
+        assert_eq!(10, grid.cols());
+        assert_eq!(50, grid.len());
+        assert_eq!(2, grid[(0, 0)]);
+        assert_eq!(8, grid[(4, 9)]);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        match Grid::<u8>::parse_digits(b"123\n1x3" as &[u8]) {
+            Err(ParseGridError::InvalidCell {
+                line,
+                column,
+                found,
+            }) => assert_eq!((2, 2, 'x'), (line, column, found)),
+            other => panic!("unexpected result {:?}", other),
+        }
+
+        match Grid::<u8>::parse_digits(b"123\n12" as &[u8]) {
+            Err(ParseGridError::RaggedRow {
+                line,
+                expected,
+                found,
+            }) => assert_eq!((2, 3, 2), (line, expected, found)),
+            other => panic!("unexpected result {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_get() {
+        let grid: Grid<u8> = Grid::parse_digits(F).unwrap();
+
+        assert_eq!(Some(&1), grid.get((0, 1)));
+        assert_eq!(None, grid.get((5, 0)));
+        assert_eq!(None, grid.get((0, 10)));
+        assert_eq!(Some((4, 9)), grid.last_pos());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_out_of_bounds() {
+        let grid: Grid<u8> = Grid::parse_digits(F).unwrap();
+        let _ = grid[(5, 0)];
+    }
+
+    #[test]
+    fn test_row_and_column() {
+        let grid: Grid<u8> = Grid::parse_digits(F).unwrap();
+
+        assert_eq!(&[3, 9, 8, 7, 8, 9, 4, 9, 2, 1], grid.row(1));
+        assert_eq!(
+            vec![1, 9, 8, 7, 8],
+            grid.column(1).copied().collect::<Vec<u8>>()
+        );
+    }
+
+    #[test]
+    fn test_neighbours4() {
+        let grid = Grid::from_elem(4, 4, 0);
+
+        let n: Vec<Pos> = grid.neighbours4((0, 0)).collect();
+        assert_eq!(vec![(1, 0), (0, 1)], n);
+
+        let n: Vec<Pos> = grid.neighbours4((3, 3)).collect();
+        assert_eq!(vec![(2, 3), (3, 2)], n);
+
+        let n: Vec<Pos> = grid.neighbours4((1, 1)).collect();
+        assert_eq!(vec![(0, 1), (2, 1), (1, 0), (1, 2)], n);
+    }
+
+    #[test]
+    fn test_neighbours8() {
+        let grid = Grid::from_elem(10, 10, 0);
+
+        let n: Vec<Pos> = grid.neighbours8((0, 0)).collect();
+        assert_eq!(vec![(0, 1), (1, 0), (1, 1)], n);
+
+        let n: Vec<Pos> = grid.neighbours8((9, 9)).collect();
+        assert_eq!(vec![(8, 8), (8, 9), (9, 8)], n);
+
+        assert_eq!(8, grid.neighbours8((1, 1)).count());
+    }
+
+    #[test]
+    fn test_display() {
+        let grid = Grid::from_vec(2, 3, vec![1, 2, 3, 4, 5, 6]).unwrap();
+        assert_eq!("123\n456\n", grid.to_string());
+
+        assert_eq!(None, Grid::from_vec(2, 2, vec![1, 2, 3]));
+    }
+
+    #[test]
+    fn test_from_fn_and_map() {
+        let grid = Grid::from_fn(2, 2, |(i, j)| i * 2 + j);
+        assert_eq!(
+            vec![0, 1, 2, 3],
+            grid.iter().copied().collect::<Vec<usize>>()
+        );
+
+        let doubled = grid.map(|v| v * 2);
+        assert_eq!(6, doubled[(1, 1)]);
+    }
+}
@@ -0,0 +1,49 @@
+use crate::error::{Error, Result};
+use std::fs;
+use std::io::{self, BufRead};
+
+/// Name used on the command line to read the input from stdin
+pub const STDIN: &str = "-";
+
+/// Opens the input at `path`, or stdin when the path is `-`
+pub fn open_input(path: &str) -> Result<Box<dyn BufRead>> {
+    if path == STDIN {
+        return Ok(Box::new(io::stdin().lock()));
+    }
+
+    match fs::File::open(path) {
+        Ok(f) => Ok(Box::new(io::BufReader::new(f))),
+        Err(why) => Err(Error::OpenInput {
+            path: String::from(path),
+            why,
+        }),
+    }
+}
+
+/// Reads the whole input at `path`, or stdin when the path is `-`
+pub fn read_input(path: &str) -> Result<Vec<u8>> {
+    let mut input = vec![];
+    open_input(path)?.read_to_end(&mut input)?;
+    Ok(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_open_input() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml");
+        let mut first_line = String::new();
+        open_input(path)
+            .unwrap()
+            .read_line(&mut first_line)
+            .unwrap();
+        assert_eq!("[package]\n", first_line);
+
+        match open_input("does_not_exist.txt") {
+            Err(Error::OpenInput { path, .. }) => assert_eq!("does_not_exist.txt", path),
+            _ => panic!("expected an OpenInput error"),
+        }
+    }
+}
@@ -0,0 +1,26 @@
+//! Code shared between the solutions of the different days
+
+pub mod answers;
+pub mod count;
+pub mod error;
+pub mod grid;
+pub mod input;
+pub mod logging;
+pub mod profile;
+pub mod report;
+pub mod rng;
+pub mod solver;
+
+pub use answers::{input_id, Answers, Verdict};
+pub use count::Count;
+pub use error::{Error, Location, Result};
+pub use grid::{Grid, ParseGridError, Pos};
+pub use input::{open_input, read_input, STDIN};
+pub use logging::init_logging;
+pub use profile::{CountingAlloc, Measure, Phase, Profile, Stats};
+pub use report::Record;
+pub use rng::Rng;
+pub use solver::{print_answer, run, Answer, Part, Solution, Solve, Solver};
+
+#[cfg(feature = "bigint")]
+pub use num_bigint::BigUint;
@@ -0,0 +1,19 @@
+use env_logger::{Builder, Env, Target};
+
+/// Environment variable with the log filters used when `--log` is not given
+pub const LOG_ENV: &str = "AOC_LOG";
+
+/// Sends the log to stderr so it doesn't mix with the answers
+///
+/// `filters` has the `env_logger` syntax: a level like `debug` for every day, or targets
+/// with their levels like `day13=debug,day15=trace`, each day logs with its crate name as
+/// target. Without filters `AOC_LOG` is used and only warnings are shown by default.
+pub fn init_logging(filters: Option<&str>) {
+    let mut builder = Builder::from_env(Env::new().filter_or(LOG_ENV, "warn"));
+    if let Some(filters) = filters {
+        builder.parse_filters(filters);
+    }
+    builder.target(Target::Stderr).format_timestamp(None);
+    // the log can only be initialized once, later calls keep the first filters
+    let _ = builder.try_init();
+}
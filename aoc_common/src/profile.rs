@@ -0,0 +1,227 @@
+use crate::error::Result;
+use crate::solver::Part;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// System allocator keeping track of the peak of allocated memory
+///
+/// Allocations are only counted in programs installing it as global allocator:
+///
+/// ```ignore
+/// #[global_allocator]
+/// static ALLOC: aoc_common::CountingAlloc = aoc_common::CountingAlloc;
+/// ```
+pub struct CountingAlloc;
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            let allocated = ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            PEAK.fetch_max(allocated, Ordering::Relaxed);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+            let allocated = ALLOCATED.fetch_add(new_size, Ordering::Relaxed) + new_size;
+            PEAK.fetch_max(allocated, Ordering::Relaxed);
+        }
+        new_ptr
+    }
+}
+
+/// Runs `f` returning the most memory it had allocated at once, 0 without `CountingAlloc`
+fn peak_during<T, F: FnOnce() -> T>(f: F) -> (T, usize) {
+    let baseline = ALLOCATED.load(Ordering::Relaxed);
+    PEAK.store(baseline, Ordering::Relaxed);
+    let val = f();
+    (val, PEAK.load(Ordering::Relaxed).saturating_sub(baseline))
+}
+
+/// Minimum, median and maximum of the times of repeated runs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Statistics of the samples, the median of an even number of samples is the lower one
+    ///
+    /// # Panics
+    ///
+    /// If there are no samples
+    pub fn new(samples: &mut [Duration]) -> Stats {
+        assert!(!samples.is_empty(), "no samples to compute statistics");
+        samples.sort();
+        Stats {
+            min: samples[0],
+            median: samples[(samples.len() - 1) / 2],
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+/// Times and peak allocated bytes of one phase run repeatedly
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Measure {
+    pub stats: Stats,
+    /// Largest peak of memory allocated by any of the runs
+    pub peak_bytes: usize,
+}
+
+/// Runs `f` `runs` times returning the value of the last run and its measure
+///
+/// # Panics
+///
+/// If `runs` is 0
+pub fn measure<T, F: FnMut() -> Result<T>>(runs: usize, mut f: F) -> Result<(T, Measure)> {
+    assert!(runs > 0, "at least one run is needed");
+    let mut samples = Vec::with_capacity(runs);
+    let mut peak_bytes = 0;
+    let mut val = None;
+    for _ in 0..runs {
+        // the value of the previous run is dropped before starting the next one
+        drop(val.take());
+        let start = Instant::now();
+        let (result, peak) = peak_during(&mut f);
+        samples.push(start.elapsed());
+        peak_bytes = peak_bytes.max(peak);
+        val = Some(result?);
+    }
+    let measure = Measure {
+        stats: Stats::new(&mut samples),
+        peak_bytes,
+    };
+    Ok((val.expect("runs is not 0"), measure))
+}
+
+/// Step of the solution of a day
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    Parse,
+    Part(Part),
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Phase::Parse => f.pad("parse"),
+            Phase::Part(part) => f.pad(&format!("part {}", part)),
+        }
+    }
+}
+
+/// Measures of every phase of one day
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Profile {
+    pub day: u32,
+    pub phases: Vec<(Phase, Measure)>,
+}
+
+fn millis(duration: Duration) -> String {
+    format!("{:.3}", duration.as_secs_f64() * 1000.0)
+}
+
+/// Formats the profiles as a table with one row per phase, times are in milliseconds
+pub fn table(profiles: &[Profile]) -> String {
+    let mut lines = vec![format!(
+        "{:>3}  {:<6}  {:>10}  {:>10}  {:>10}  {:>12}",
+        "day", "phase", "min ms", "median ms", "max ms", "peak bytes"
+    )];
+    for profile in profiles {
+        for (phase, measure) in &profile.phases {
+            lines.push(format!(
+                "{:>3}  {:<6}  {:>10}  {:>10}  {:>10}  {:>12}",
+                format!("{:02}", profile.day),
+                phase,
+                millis(measure.stats.min),
+                millis(measure.stats.median),
+                millis(measure.stats.max),
+                measure.peak_bytes
+            ));
+        }
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::error::Error;
+
+    #[test]
+    fn test_stats() {
+        let ms = Duration::from_millis;
+        let stats = Stats::new(&mut [ms(5), ms(1), ms(3), ms(2)]);
+        assert_eq!(
+            Stats {
+                min: ms(1),
+                median: ms(2),
+                max: ms(5)
+            },
+            stats
+        );
+        assert_eq!(ms(4), Stats::new(&mut [ms(4)]).median);
+    }
+
+    #[test]
+    fn test_measure() {
+        let mut calls = 0;
+        let (val, _) = measure(3, || {
+            calls += 1;
+            Ok(calls)
+        })
+        .unwrap();
+        assert_eq!(3, val);
+
+        let mut calls = 0;
+        let result = measure(3, || {
+            calls += 1;
+            match calls {
+                2 => Err(Error::NoSolution(String::from("second run"))),
+                _ => Ok(calls),
+            }
+        });
+        assert!(result.is_err());
+        assert_eq!(2, calls);
+    }
+
+    #[test]
+    fn test_table() {
+        let ms = Duration::from_millis;
+        let measure = Measure {
+            stats: Stats {
+                min: ms(1),
+                median: ms(2),
+                max: Duration::from_micros(3500),
+            },
+            peak_bytes: 2048,
+        };
+        let profile = Profile {
+            day: 7,
+            phases: vec![(Phase::Parse, measure), (Phase::Part(Part::Two), measure)],
+        };
+        assert_eq!(
+            "day  phase       min ms   median ms      max ms    peak bytes
+ 07  parse        1.000       2.000       3.500          2048
+ 07  part 2       1.000       2.000       3.500          2048",
+            table(&[profile])
+        );
+    }
+}
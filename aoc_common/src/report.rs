@@ -0,0 +1,64 @@
+use crate::solver::{Answer, Part, Solution};
+use serde::Serialize;
+
+/// Machine readable report of one answer, printed as one line of JSON
+///
+/// ```json
+/// {"day":1,"part":2,"answer":1257,"elapsed_ms":0.012,"input":"8f916f0e8be8e646"}
+/// ```
+#[derive(Debug, Serialize)]
+pub struct Record<'a> {
+    pub day: u32,
+    pub part: Part,
+    /// Numbers are JSON numbers, text answers are strings
+    pub answer: &'a Answer,
+    /// Time solving the part with microsecond resolution, without parsing the input
+    pub elapsed_ms: f64,
+    /// Identifier of the input, see `input_id`
+    pub input: &'a str,
+}
+
+impl<'a> Record<'a> {
+    pub fn new(day: u32, input: &'a str, solution: &'a Solution) -> Record<'a> {
+        Record {
+            day,
+            part: solution.part,
+            answer: &solution.answer,
+            elapsed_ms: solution.elapsed.as_micros() as f64 / 1000.0,
+            input,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("records are always serializable")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_to_json() {
+        let solution = Solution {
+            part: Part::Two,
+            answer: Answer::UInt(1257),
+            elapsed: Duration::from_micros(1500),
+        };
+        assert_eq!(
+            r#"{"day":1,"part":2,"answer":1257,"elapsed_ms":1.5,"input":"8f916f0e8be8e646"}"#,
+            Record::new(1, "8f916f0e8be8e646", &solution).to_json()
+        );
+
+        let solution = Solution {
+            part: Part::One,
+            answer: Answer::Text(String::from("#.\n.#")),
+            elapsed: Duration::ZERO,
+        };
+        assert_eq!(
+            r##"{"day":13,"part":1,"answer":"#.\n.#","elapsed_ms":0.0,"input":"ff"}"##,
+            Record::new(13, "ff", &solution).to_json()
+        );
+    }
+}
@@ -0,0 +1,53 @@
+/// Seeded xorshift generator, for random inputs that are the same on every run
+///
+/// Not meant for anything needing unpredictable numbers.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    /// Never 0, xorshift stays at 0 forever
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        // mixes the seed so close seeds give unrelated sequences, and 0 is a valid seed
+        let state = seed.wrapping_mul(0x9e37_79b9_7f4a_7c15) ^ 0x2545_f491_4f6c_dd1d;
+        Rng {
+            state: if state == 0 { 1 } else { state },
+        }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    /// Number in `0..bound`
+    ///
+    /// # Panics
+    ///
+    /// If `bound` is 0
+    pub fn below(&mut self, bound: u64) -> u64 {
+        self.next_u64() % bound
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_seeds() {
+        let sequence = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..8).map(|_| rng.next_u64()).collect::<Vec<u64>>()
+        };
+        assert_eq!(sequence(7), sequence(7));
+        assert_ne!(sequence(7), sequence(8));
+        assert!(sequence(0).iter().all(|n| *n != 0));
+
+        let mut rng = Rng::new(1);
+        assert!((0..100).all(|_| rng.below(6) < 6));
+    }
+}
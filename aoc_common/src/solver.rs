@@ -0,0 +1,295 @@
+use crate::answers::input_id;
+use crate::error::{Error, Result};
+use crate::input::read_input;
+use crate::logging::init_logging;
+use crate::profile::{measure, Phase, Profile};
+use crate::report::Record;
+use serde::{Serialize, Serializer};
+use std::env;
+use std::fmt;
+use std::io::BufRead;
+use std::process;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// Answer of one part of a puzzle
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum Answer {
+    Int(i64),
+    UInt(u64),
+    /// Answers that are not numbers, like the letters drawn on day 13
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(val) => write!(f, "{}", val),
+            Answer::UInt(val) => write!(f, "{}", val),
+            Answer::Text(val) => write!(f, "{}", val),
+        }
+    }
+}
+
+macro_rules! answer_from {
+    ($variant:ident, $target:ty, $($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(val: $t) -> Self {
+                    Answer::$variant(val as $target)
+                }
+            }
+        )*
+    };
+}
+
+answer_from!(Int, i64, i32, i64);
+answer_from!(UInt, u64, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(val: String) -> Self {
+        Answer::Text(val)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const BOTH: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+impl Serialize for Part {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        match self {
+            Part::One => serializer.serialize_u8(1),
+            Part::Two => serializer.serialize_u8(2),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Part> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(Error::Usage(format!("invalid part {}", s))),
+        }
+    }
+}
+
+/// Solution of one day: the input is parsed once and shared by both parts
+pub trait Solver {
+    /// Day of the calendar solved
+    const DAY: u32;
+
+    type Input;
+
+    fn parse<R: BufRead>(&self, reader: R) -> Result<Self::Input>;
+    fn part1(&self, input: &Self::Input) -> Result<Answer>;
+    fn part2(&self, input: &Self::Input) -> Result<Answer>;
+}
+
+/// Answer of one part and the time it took to compute it, not counting the parsing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solution {
+    pub part: Part,
+    pub answer: Answer,
+    pub elapsed: Duration,
+}
+
+/// Object safe version of `Solver` so different days can be stored together
+pub trait Solve {
+    fn day(&self) -> u32;
+    fn solve(&self, reader: &mut dyn BufRead, parts: &[Part]) -> Result<Vec<Solution>>;
+    /// Parses the input and solves the parts `runs` times measuring every phase
+    fn profile(&self, input: &[u8], parts: &[Part], runs: usize) -> Result<Profile>;
+}
+
+impl<S: Solver> Solve for S {
+    fn day(&self) -> u32 {
+        S::DAY
+    }
+
+    fn solve(&self, reader: &mut dyn BufRead, parts: &[Part]) -> Result<Vec<Solution>> {
+        let input = self.parse(reader)?;
+        let mut solutions = Vec::with_capacity(parts.len());
+        for part in parts {
+            let start = Instant::now();
+            let answer = match part {
+                Part::One => self.part1(&input)?,
+                Part::Two => self.part2(&input)?,
+            };
+            solutions.push(Solution {
+                part: *part,
+                answer,
+                elapsed: start.elapsed(),
+            });
+        }
+        Ok(solutions)
+    }
+
+    fn profile(&self, input: &[u8], parts: &[Part], runs: usize) -> Result<Profile> {
+        let (parsed, parse) = measure(runs, || self.parse(input))?;
+        let mut phases = vec![(Phase::Parse, parse)];
+        for part in parts {
+            let (_, measure) = measure(runs, || match part {
+                Part::One => self.part1(&parsed),
+                Part::Two => self.part2(&parsed),
+            })?;
+            phases.push((Phase::Part(*part), measure));
+        }
+        Ok(Profile {
+            day: S::DAY,
+            phases,
+        })
+    }
+}
+
+/// Prints an answer after its label, answers spanning several lines start on a new line
+pub fn print_answer(label: &str, answer: &Answer) {
+    let answer = answer.to_string();
+    if answer.contains('\n') {
+        println!("{}:\n{}", label, answer);
+    } else {
+        println!("{}: {}", label, answer);
+    }
+}
+
+/// Solves both parts printing the answers, exits with an error code if the input can't be solved
+///
+/// The input is read from the path given as program argument, or from stdin if it is `-`.
+/// Without arguments `default_input` is used. With `--json` every answer is printed as
+/// a JSON `Record`, `--log FILTERS` shows the log of the solver, see `init_logging`.
+pub fn run<S: Solver>(solver: &S, default_input: &str) {
+    let mut path = String::from(default_input);
+    let mut json = false;
+    let mut log = None;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => json = true,
+            "--log" => log = args.next(),
+            _ => path = arg,
+        }
+    }
+    init_logging(log.as_deref());
+
+    let solutions = read_input(&path).and_then(|input| {
+        let solutions = solver.solve(&mut input.as_slice(), &Part::BOTH)?;
+        Ok((input_id(&input), solutions))
+    });
+
+    match solutions {
+        Ok((id, solutions)) => {
+            for solution in &solutions {
+                if json {
+                    println!("{}", Record::new(S::DAY, &id, solution).to_json());
+                } else {
+                    print_answer(&format!("Part {}", solution.part), &solution.answer);
+                }
+            }
+        }
+        Err(why) => {
+            eprintln!("Error: {}", why);
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Sum;
+
+    impl Solver for Sum {
+        const DAY: u32 = 0;
+
+        type Input = Vec<u32>;
+
+        fn parse<R: BufRead>(&self, reader: R) -> Result<Vec<u32>> {
+            let mut vals = vec![];
+            for line in reader.lines() {
+                let line = line?;
+                match line.trim().parse::<u32>() {
+                    Ok(val) => vals.push(val),
+                    Err(_) => return Err(Error::InvalidInput(line)),
+                }
+            }
+            Ok(vals)
+        }
+
+        fn part1(&self, input: &Vec<u32>) -> Result<Answer> {
+            Ok(input.iter().sum::<u32>().into())
+        }
+
+        fn part2(&self, input: &Vec<u32>) -> Result<Answer> {
+            Ok(input.iter().product::<u32>().into())
+        }
+    }
+
+    fn answers(solutions: Vec<Solution>) -> Vec<(Part, Answer)> {
+        solutions.into_iter().map(|s| (s.part, s.answer)).collect()
+    }
+
+    #[test]
+    fn test_solve() {
+        let mut reader = b"1\n2\n3" as &[u8];
+        let solutions = Sum.solve(&mut reader, &Part::BOTH).unwrap();
+
+        assert_eq!(
+            vec![(Part::One, Answer::UInt(6)), (Part::Two, Answer::UInt(6))],
+            answers(solutions)
+        );
+
+        let mut reader = b"2\n3" as &[u8];
+        let solutions = Sum.solve(&mut reader, &[Part::Two]).unwrap();
+        assert_eq!(vec![(Part::Two, Answer::UInt(6))], answers(solutions));
+    }
+
+    #[test]
+    fn test_profile() {
+        let profile = Sum.profile(b"1\n2\n3", &[Part::Two], 3).unwrap();
+        assert_eq!(0, profile.day);
+        let phases: Vec<Phase> = profile.phases.iter().map(|(phase, _)| *phase).collect();
+        assert_eq!(vec![Phase::Parse, Phase::Part(Part::Two)], phases);
+
+        assert!(Sum.profile(b"1\nx", &Part::BOTH, 3).is_err());
+    }
+
+    #[test]
+    fn test_solve_invalid_input() {
+        let mut reader = b"1\nx" as &[u8];
+        assert!(Sum.solve(&mut reader, &Part::BOTH).is_err());
+    }
+
+    #[test]
+    fn test_part_from_str() {
+        assert_eq!(Part::One, "1".parse::<Part>().unwrap());
+        assert_eq!(Part::Two, "2".parse::<Part>().unwrap());
+        assert!("3".parse::<Part>().is_err());
+    }
+
+    #[test]
+    fn test_answer_display() {
+        assert_eq!("-3", Answer::from(-3_i64).to_string());
+        assert_eq!("42", Answer::from(42_usize).to_string());
+        assert_eq!("#.#", Answer::from(String::from("#.#")).to_string());
+    }
+}
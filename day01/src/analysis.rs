@@ -0,0 +1,267 @@
+use std::collections::VecDeque;
+use std::iter::FromIterator;
+
+/// Function reducing the measurements of a window to a single value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Aggregate {
+    Sum,
+    Mean,
+    Min,
+    Max,
+    /// Middle value, the mean of the two middle values for windows of even size
+    Median,
+}
+
+impl Aggregate {
+    /// Value of the window, 0 for an empty one
+    pub fn apply(&self, window: &[u32]) -> f64 {
+        if window.is_empty() {
+            return 0.0;
+        }
+        let values = window.iter().map(|val| f64::from(*val));
+        match self {
+            Aggregate::Sum => values.sum(),
+            Aggregate::Mean => values.sum::<f64>() / window.len() as f64,
+            Aggregate::Min => values.fold(f64::INFINITY, f64::min),
+            Aggregate::Max => values.fold(f64::NEG_INFINITY, f64::max),
+            Aggregate::Median => {
+                let mut sorted: Vec<f64> = values.collect();
+                sorted.sort_by(f64::total_cmp);
+                let middle = sorted.len() / 2;
+                if sorted.len().is_multiple_of(2) {
+                    (sorted[middle - 1] + sorted[middle]) / 2.0
+                } else {
+                    sorted[middle]
+                }
+            }
+        }
+    }
+}
+
+/// Aggregated values of every window of `size` consecutive measurements
+///
+/// There are no windows when there are fewer than `size` measurements.
+///
+/// # Panics
+///
+/// If `size` is 0
+pub fn aggregate_windows(
+    depths: &[u32],
+    size: usize,
+    aggregate: Aggregate,
+) -> impl Iterator<Item = f64> + '_ {
+    depths
+        .windows(size)
+        .map(move |window| aggregate.apply(window))
+}
+
+/// How a sequence of values changes from one value to the next
+///
+/// Built by collecting the values, e.g. `let trend: Trend = depths.iter().collect();`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Trend {
+    pub increases: usize,
+    pub decreases: usize,
+    /// Values equal to the previous one
+    pub plateaus: usize,
+    /// Number of values of the longest strictly increasing run
+    pub longest_increasing_run: usize,
+}
+
+impl<T: PartialOrd> FromIterator<T> for Trend {
+    fn from_iter<I: IntoIterator<Item = T>>(values: I) -> Trend {
+        values
+            .into_iter()
+            .running_trend()
+            .last()
+            .unwrap_or_default()
+    }
+}
+
+/// Iterator adapter yielding the aggregated value of every window of `size` consecutive
+/// measurements, it only keeps the current window in memory
+///
+/// Created with `SonarIterator::sliding`.
+pub struct Sliding<I> {
+    depths: I,
+    window: VecDeque<u32>,
+    size: usize,
+    aggregate: Aggregate,
+}
+
+impl<I: Iterator<Item = u32>> Iterator for Sliding<I> {
+    type Item = f64;
+
+    fn next(&mut self) -> Option<f64> {
+        while self.window.len() < self.size {
+            self.window.push_back(self.depths.next()?);
+        }
+        let val = self.aggregate.apply(self.window.make_contiguous());
+        self.window.pop_front();
+        Some(val)
+    }
+}
+
+/// Iterator adapter yielding the trend of the values seen so far after every value
+///
+/// Created with `SonarIterator::running_trend`.
+pub struct RunningTrend<I: Iterator> {
+    values: I,
+    prev: Option<I::Item>,
+    run: usize,
+    trend: Trend,
+}
+
+impl<I> Iterator for RunningTrend<I>
+where
+    I: Iterator,
+    I::Item: PartialOrd,
+{
+    type Item = Trend;
+
+    fn next(&mut self) -> Option<Trend> {
+        let val = self.values.next()?;
+        self.run = match &self.prev {
+            Some(prev) if val > *prev => {
+                self.trend.increases += 1;
+                self.run + 1
+            }
+            Some(prev) if val < *prev => {
+                self.trend.decreases += 1;
+                1
+            }
+            Some(_) => {
+                self.trend.plateaus += 1;
+                1
+            }
+            None => 1,
+        };
+        self.trend.longest_increasing_run = self.trend.longest_increasing_run.max(self.run);
+        self.prev = Some(val);
+        Some(self.trend)
+    }
+}
+
+/// Adapters analysing a stream of measurements as they arrive, without storing it
+pub trait SonarIterator: Iterator + Sized {
+    /// Aggregates every window of `size` consecutive measurements
+    ///
+    /// # Panics
+    ///
+    /// If `size` is 0
+    fn sliding(self, size: usize, aggregate: Aggregate) -> Sliding<Self>
+    where
+        Self: Iterator<Item = u32>,
+    {
+        assert!(size > 0, "window size must be at least 1");
+        Sliding {
+            depths: self,
+            window: VecDeque::with_capacity(size),
+            size,
+            aggregate,
+        }
+    }
+
+    /// Trend of the values seen so far after every value
+    fn running_trend(self) -> RunningTrend<Self>
+    where
+        Self::Item: PartialOrd,
+    {
+        RunningTrend {
+            values: self,
+            prev: None,
+            run: 0,
+            trend: Trend::default(),
+        }
+    }
+}
+
+impl<I: Iterator> SonarIterator for I {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEPTHS: [u32; 10] = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+
+    #[test]
+    fn test_aggregate_windows() {
+        let sums: Vec<f64> = aggregate_windows(&DEPTHS, 3, Aggregate::Sum).collect();
+        assert_eq!(
+            vec![607.0, 618.0, 618.0, 617.0, 647.0, 716.0, 769.0, 792.0],
+            sums
+        );
+
+        let means: Vec<f64> = aggregate_windows(&DEPTHS[..4], 2, Aggregate::Mean).collect();
+        assert_eq!(vec![199.5, 204.0, 209.0], means);
+
+        let mins: Vec<f64> = aggregate_windows(&DEPTHS[2..6], 2, Aggregate::Min).collect();
+        assert_eq!(vec![208.0, 200.0, 200.0], mins);
+
+        let maxs: Vec<f64> = aggregate_windows(&DEPTHS[2..6], 4, Aggregate::Max).collect();
+        assert_eq!(vec![210.0], maxs);
+
+        let medians: Vec<f64> = aggregate_windows(&DEPTHS[2..6], 3, Aggregate::Median).collect();
+        assert_eq!(vec![208.0, 207.0], medians);
+        assert_eq!(204.0, Aggregate::Median.apply(&DEPTHS[1..3]));
+
+        assert_eq!(
+            0,
+            aggregate_windows(&DEPTHS[..2], 3, Aggregate::Sum).count()
+        );
+    }
+
+    #[test]
+    fn test_trend() {
+        let trend: Trend = DEPTHS.iter().collect();
+        assert_eq!(
+            Trend {
+                increases: 7,
+                decreases: 2,
+                plateaus: 0,
+                longest_increasing_run: 4,
+            },
+            trend
+        );
+
+        let sums = aggregate_windows(&DEPTHS, 3, Aggregate::Sum);
+        let trend: Trend = sums.collect();
+        assert_eq!(5, trend.increases);
+        assert_eq!(1, trend.plateaus);
+        assert_eq!(1, trend.decreases);
+        assert_eq!(5, trend.longest_increasing_run);
+
+        assert_eq!(Trend::default(), Vec::<u32>::new().into_iter().collect());
+    }
+
+    #[test]
+    fn test_sliding() {
+        let sums: Vec<f64> = DEPTHS.iter().copied().sliding(3, Aggregate::Sum).collect();
+        let expected: Vec<f64> = aggregate_windows(&DEPTHS, 3, Aggregate::Sum).collect();
+        assert_eq!(expected, sums);
+
+        let depths: Vec<f64> = DEPTHS.iter().copied().sliding(1, Aggregate::Max).collect();
+        assert_eq!(
+            DEPTHS.iter().map(|d| f64::from(*d)).collect::<Vec<_>>(),
+            depths
+        );
+
+        assert_eq!(
+            0,
+            DEPTHS[..2]
+                .iter()
+                .copied()
+                .sliding(3, Aggregate::Sum)
+                .count()
+        );
+    }
+
+    #[test]
+    fn test_running_trend() {
+        let increases: Vec<usize> = DEPTHS.iter().running_trend().map(|t| t.increases).collect();
+        assert_eq!(vec![0, 1, 2, 3, 3, 4, 5, 6, 6, 7], increases);
+
+        let last = DEPTHS.iter().running_trend().last().unwrap();
+        assert_eq!(DEPTHS.iter().collect::<Trend>(), last);
+    }
+}
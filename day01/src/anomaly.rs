@@ -0,0 +1,214 @@
+use crate::analysis::{aggregate_windows, Aggregate};
+use std::fmt;
+use std::io::{self, BufRead};
+
+/// What is suspicious about a line of the sonar sweep
+#[derive(Debug, Clone, PartialEq)]
+pub enum AnomalyKind {
+    /// Change from the previous depth larger than the jump threshold
+    Jump {
+        from: u32,
+        to: u32,
+    },
+    /// Depth further from the median of the depths around it than the outlier threshold
+    Outlier {
+        depth: u32,
+        median: f64,
+    },
+    /// Line that is not a number
+    NotANumber(String),
+    Negative(i64),
+}
+
+/// Suspicious line with the lines around it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Anomaly {
+    /// Line of the reading, starting at 1
+    pub line: usize,
+    pub kind: AnomalyKind,
+    /// Line number of the first line of `window`
+    pub window_start: usize,
+    /// Lines centered on the anomaly, fewer at the start and end of the sweep
+    pub window: Vec<String>,
+}
+
+impl fmt::Display for Anomaly {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        match &self.kind {
+            AnomalyKind::Jump { from, to } => write!(f, "jump from {} to {}", from, to)?,
+            AnomalyKind::Outlier { depth, median } => {
+                write!(f, "outlier {} around median {}", depth, median)?
+            }
+            AnomalyKind::NotANumber(token) => write!(f, "not a number '{}'", token)?,
+            AnomalyKind::Negative(depth) => write!(f, "negative depth {}", depth)?,
+        }
+        let window_end = self.window_start + self.window.len() - 1;
+        write!(
+            f,
+            " (lines {}-{}: {})",
+            self.window_start,
+            window_end,
+            self.window.join(" ")
+        )
+    }
+}
+
+/// Thresholds of the anomaly detection
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Detector {
+    /// Largest change between consecutive depths that is not a jump
+    pub jump: u32,
+    /// Number of depths of the rolling median, and of lines of the reported windows
+    pub window: usize,
+    /// Largest distance to the rolling median that is not an outlier
+    pub outlier: f64,
+}
+
+impl Default for Detector {
+    fn default() -> Detector {
+        Detector {
+            jump: 50,
+            window: 5,
+            outlier: 50.0,
+        }
+    }
+}
+
+impl Detector {
+    /// Anomalies of the sweep sorted by line, invalid lines are reported instead of failing
+    ///
+    /// Only depths with `window / 2` depths on each side are checked for outliers.
+    ///
+    /// # Panics
+    ///
+    /// If `window` is 0
+    pub fn detect<I: BufRead>(&self, reader: I) -> io::Result<Vec<Anomaly>> {
+        assert!(self.window > 0, "window size must be at least 1");
+        let lines = reader.lines().collect::<io::Result<Vec<String>>>()?;
+
+        let mut kinds = vec![];
+        let mut readings: Vec<(usize, u32)> = vec![];
+        for (cnt, line) in lines.iter().enumerate() {
+            let token = line.trim();
+            match token.parse::<i64>() {
+                Ok(depth) if depth < 0 => kinds.push((cnt, AnomalyKind::Negative(depth))),
+                Ok(depth) if depth <= u32::MAX as i64 => readings.push((cnt, depth as u32)),
+                _ => kinds.push((cnt, AnomalyKind::NotANumber(token.to_string()))),
+            }
+        }
+
+        for pair in readings.windows(2) {
+            let ((_, from), (cnt, to)) = (pair[0], pair[1]);
+            if from.abs_diff(to) > self.jump {
+                kinds.push((cnt, AnomalyKind::Jump { from, to }));
+            }
+        }
+
+        let depths: Vec<u32> = readings.iter().map(|(_, depth)| *depth).collect();
+        let medians = aggregate_windows(&depths, self.window, Aggregate::Median);
+        for (&(cnt, depth), median) in readings.iter().skip(self.window / 2).zip(medians) {
+            if (f64::from(depth) - median).abs() > self.outlier {
+                kinds.push((cnt, AnomalyKind::Outlier { depth, median }));
+            }
+        }
+
+        kinds.sort_by_key(|(cnt, _)| *cnt);
+        let half = self.window / 2;
+        Ok(kinds
+            .into_iter()
+            .map(|(cnt, kind)| {
+                let start = cnt.saturating_sub(half);
+                let end = (cnt + self.window - half).min(lines.len());
+                Anomaly {
+                    line: cnt + 1,
+                    kind,
+                    window_start: start + 1,
+                    window: lines[start..end].to_vec(),
+                }
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SWEEP: &str = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n";
+
+    fn kinds(anomalies: &[Anomaly]) -> Vec<(usize, AnomalyKind)> {
+        anomalies.iter().map(|a| (a.line, a.kind.clone())).collect()
+    }
+
+    #[test]
+    fn test_detect_clean_sweep() {
+        let anomalies = Detector::default().detect(SWEEP.as_bytes()).unwrap();
+        assert!(anomalies.is_empty(), "{:?}", anomalies);
+    }
+
+    #[test]
+    fn test_detect() {
+        let sweep = "199\n200\n208\n2100\n200\n-7\nx\n269\n260\n263\n";
+        let detector = Detector {
+            outlier: 100.0,
+            ..Detector::default()
+        };
+        let anomalies = detector.detect(sweep.as_bytes()).unwrap();
+        assert_eq!(
+            vec![
+                (
+                    4,
+                    AnomalyKind::Jump {
+                        from: 208,
+                        to: 2100
+                    }
+                ),
+                (
+                    4,
+                    AnomalyKind::Outlier {
+                        depth: 2100,
+                        median: 208.0
+                    }
+                ),
+                (
+                    5,
+                    AnomalyKind::Jump {
+                        from: 2100,
+                        to: 200
+                    }
+                ),
+                (6, AnomalyKind::Negative(-7)),
+                (7, AnomalyKind::NotANumber(String::from("x"))),
+                (8, AnomalyKind::Jump { from: 200, to: 269 }),
+            ],
+            kinds(&anomalies)
+        );
+
+        assert_eq!(2, anomalies[0].window_start);
+        assert_eq!(vec!["200", "208", "2100", "200", "-7"], anomalies[0].window);
+        assert_eq!(
+            "line 7: not a number 'x' (lines 5-9: 200 -7 x 269 260)",
+            anomalies[4].to_string()
+        );
+    }
+
+    #[test]
+    fn test_detect_window_at_edges() {
+        let detector = Detector {
+            jump: 5,
+            window: 3,
+            outlier: 1000.0,
+        };
+        let anomalies = detector.detect("1\n100\n".as_bytes()).unwrap();
+        assert_eq!(1, anomalies.len());
+        assert_eq!(1, anomalies[0].window_start);
+        assert_eq!(vec!["1", "100"], anomalies[0].window);
+    }
+
+    #[test]
+    fn test_detect_short_sweep() {
+        let anomalies = Detector::default().detect("199\n".as_bytes()).unwrap();
+        assert!(anomalies.is_empty());
+    }
+}
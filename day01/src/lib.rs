@@ -0,0 +1,215 @@
+//! Day 1: Sonar Sweep, counts how many times the sea floor depth increases
+
+pub mod analysis;
+pub mod anomaly;
+
+pub use analysis::{aggregate_windows, Aggregate, SonarIterator, Trend};
+pub use anomaly::{Anomaly, AnomalyKind, Detector};
+
+use aoc_common::{Answer, Location, Result, Solver};
+use std::io::{self, BufRead};
+
+aoc_common::parse_error! {
+    /// Errors found parsing the depth measurements
+    pub enum ParseError {
+        /// A line that is not a depth measurement
+        InvalidDepth(Location),
+    }
+
+    fn fmt(&self, f) {
+        ParseError::InvalidDepth(loc) => write!(f, "{}: invalid depth '{}'", loc, loc.token),
+    }
+}
+
+type ParseResult<T> = std::result::Result<T, ParseError>;
+
+/// Counts how many times a number is greater than the previous one, see `Trend` for the
+/// decreases and plateaus
+pub fn count_increments(collection: &[u32]) -> u32 {
+    collection.iter().collect::<Trend>().increases as u32
+}
+
+/// Sums of every window of three consecutive measurements, see `aggregate_windows` for
+/// other sizes and aggregates
+pub fn make_sliding_window(collection: &[u32]) -> Vec<u32> {
+    collection
+        .windows(3)
+        .map(|window| window.iter().sum())
+        .collect()
+}
+
+/// Iterator reading one depth measurement per line as they arrive
+pub struct Depths<I> {
+    lines: io::Lines<I>,
+    line_no: usize,
+}
+
+/// Reads the depths of `reader` lazily, only the current line is kept in memory
+pub fn depths<I: BufRead>(reader: I) -> Depths<I> {
+    Depths {
+        lines: reader.lines(),
+        line_no: 0,
+    }
+}
+
+impl<I: BufRead> Iterator for Depths<I> {
+    type Item = ParseResult<u32>;
+
+    fn next(&mut self) -> Option<ParseResult<u32>> {
+        let line = match self.lines.next()? {
+            Ok(line) => line,
+            Err(why) => return Some(Err(why.into())),
+        };
+        self.line_no += 1;
+        let token = line.trim();
+        match token.parse::<u32>() {
+            Ok(val) => Some(Ok(val)),
+            Err(_) => Some(Err(ParseError::InvalidDepth(Location::in_line(
+                self.line_no,
+                &line,
+                token,
+            )))),
+        }
+    }
+}
+
+/// Feeds the depths of `reader` to `f` as they are read, stopping at the first invalid one
+///
+/// `f` sees the valid depths before the error, which is returned instead of its result.
+///
+/// ```
+/// use day01::{with_depths, SonarIterator};
+///
+/// let reader = "199\n200\n208\n210\n200\n".as_bytes();
+/// let increases = with_depths(reader, |depths| {
+///     depths.running_trend().map(|trend| trend.increases).last()
+/// });
+/// assert_eq!(Some(3), increases.unwrap());
+/// ```
+pub fn with_depths<I, F, T>(reader: I, f: F) -> ParseResult<T>
+where
+    I: BufRead,
+    F: FnOnce(&mut dyn Iterator<Item = u32>) -> T,
+{
+    let mut error = None;
+    let mut valid = depths(reader).map_while(|depth| match depth {
+        Ok(depth) => Some(depth),
+        Err(why) => {
+            error = Some(why);
+            None
+        }
+    });
+    let val = f(&mut valid);
+    match error {
+        Some(why) => Err(why),
+        None => Ok(val),
+    }
+}
+
+/// Reads one depth measurement per line
+pub fn parse_depths<I: BufRead>(reader: I) -> ParseResult<Vec<u32>> {
+    depths(reader).collect()
+}
+
+/// Solver of the day, part 1 counts the increments of the measurements and part 2 those
+/// of the sliding windows
+pub struct Day01;
+
+impl Solver for Day01 {
+    const DAY: u32 = 1;
+
+    type Input = Vec<u32>;
+
+    fn parse<R: BufRead>(&self, reader: R) -> Result<Vec<u32>> {
+        Ok(parse_depths(reader)?)
+    }
+
+    fn part1(&self, numbers: &Vec<u32>) -> Result<Answer> {
+        Ok(count_increments(numbers).into())
+    }
+
+    fn part2(&self, numbers: &Vec<u32>) -> Result<Answer> {
+        let window = make_sliding_window(numbers);
+        Ok(count_increments(&window).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_count() {
+        let test_cases = [
+            (vec![0, 0, 1, 1, 2, 3, 3, 4, 5], 5, "1"),
+            (vec![0, 0, 0, 0, 0, 0, 0, 0, 0], 0, "2"),
+            (vec![1, 2, 1, 2, 1, 2, 1, 2], 4, "3"),
+        ];
+
+        for test_case in test_cases {
+            assert_eq!(
+                count_increments(test_case.0.as_slice()),
+                test_case.1,
+                "test #{:02}",
+                test_case.2
+            );
+        }
+    }
+
+    #[test]
+    fn test_smooth_count() {
+        let test_cases = [
+            (
+                vec![199, 200, 208, 210, 200, 207, 240, 269, 260, 263],
+                5,
+                "1",
+            ),
+            (vec![199], 0, "2"),
+            (vec![199, 200], 0, "3"),
+            (vec![199, 200, 208], 0, "4"),
+            (vec![199, 200, 208, 210], 1, "5"),
+        ];
+
+        for test_case in test_cases {
+            let counter = make_sliding_window(test_case.0.as_slice());
+            assert_eq!(
+                count_increments(counter.as_slice()),
+                test_case.1,
+                "test #{:02}",
+                test_case.2
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_depths() {
+        let depths = parse_depths("199\n 200\n208\n".as_bytes()).unwrap();
+        assert_eq!(vec![199, 200, 208], depths);
+
+        match parse_depths("199\n  2x0\n".as_bytes()) {
+            Err(ParseError::InvalidDepth(loc)) => assert_eq!(Location::new(2, 3, "2x0"), loc),
+            _ => panic!("expected an invalid depth"),
+        }
+    }
+
+    #[test]
+    fn test_with_depths() {
+        let reader = "199\n200\n208\n210\n200\n207\n".as_bytes();
+        let windows = with_depths(reader, |depths| {
+            depths
+                .sliding(3, Aggregate::Sum)
+                .running_trend()
+                .map(|trend| trend.increases)
+                .collect::<Vec<_>>()
+        });
+        assert_eq!(vec![0, 1, 1, 1], windows.unwrap());
+
+        let mut seen = vec![];
+        let reader = "199\n200\nx\n208\n".as_bytes();
+        match with_depths(reader, |depths| seen.extend(depths)) {
+            Err(ParseError::InvalidDepth(loc)) => assert_eq!(Location::new(3, 1, "x"), loc),
+            _ => panic!("expected an invalid depth"),
+        }
+        assert_eq!(vec![199, 200], seen);
+    }
+}
@@ -1,131 +1,79 @@
-use std::fs;
-use std::io::{self, BufRead};
+use aoc_common::{open_input, Error, Result, STDIN};
+use day01::{with_depths, Aggregate, Day01, Detector, SonarIterator};
+use std::env;
+use std::process;
 
-// Read a sequence and count how many times the next number is greater than the previous
-//
+const USAGE: &str = "usage: day01 [PATH|-] [--json] [--log FILTERS]
+       day01 --stream [--window N] [PATH|-]
+       day01 --anomalies [--jump N] [--window N] [--outlier N] [PATH|-]";
 
-fn count_increments(collection: &[u32]) -> u32 {
-    let mut iter = collection.into_iter();
-    if iter.len() < 2 {
-        return 0;
-    }
-    let mut prev_val = match iter.next() {
-        Some(it) => it,
-        _ => unreachable!(),
-    };
-    let mut counter = 0;
-    for val in iter {
-        if val > prev_val {
-            counter += 1;
-        }
-        prev_val = val
-    }
-    counter
+fn invalid(msg: &str) -> Error {
+    Error::Usage(String::from(msg))
 }
 
-fn make_sliding_window(collection: &[u32]) -> Vec<u32> {
-    let mut iter = collection.into_iter();
-    let mut result: Vec<u32> = Vec::new();
-
-    if iter.len() < 4 {
-        return result;
+/// Returns the number following `flag`, it must be positive
+fn positive<I: Iterator<Item = String>>(args: &mut I, flag: &str) -> Result<usize> {
+    match args.next().map(|n| n.parse::<usize>()) {
+        Some(Ok(n)) if n > 0 => Ok(n),
+        _ => Err(invalid(&format!("invalid value for {}", flag))),
     }
+}
 
-    let mut one = iter.next().unwrap();
-    let mut two = iter.next().unwrap();
-
-    for val in iter {
-        result.push(one + two + val);
-        one = two;
-        two = val;
+/// Prints the running number of increments of the sums of `--window` depths as they arrive
+fn stream<I: Iterator<Item = String>>(mut args: I) -> Result<()> {
+    let mut path = String::from(STDIN);
+    let mut window = 1;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--window" => window = positive(&mut args, &arg)?,
+            _ => path = arg,
+        }
     }
 
-    result
+    with_depths(open_input(&path)?, |depths| {
+        let trends = depths.sliding(window, Aggregate::Sum).running_trend();
+        for (cnt, trend) in trends.enumerate() {
+            println!("{} {}", cnt + window, trend.increases);
+        }
+    })?;
+    Ok(())
 }
-/*
- *
- */
-fn main() {
-    let file_name = "input_01.txt";
-    let solution1 = 1228;
-    let solution2 = 1257;
-
-    /*
-    let f = match fs::File::open(file_name) {
-        Err(why) => panic!("Count not open {}: {}", file_name, why),
-        Ok(file) => file,
-    };
-
-    let lines = io::BufReader::new(f).lines();
-    let mut numbers = vec![];
 
-    for line in lines {
-        match line {
-            Ok(value) => numbers.push(value.parse::<u32>().unwrap()),
-            Err(why) => println!("Error when reading file: {}", why),
+/// Prints the suspicious lines of the sweep, one per line
+fn anomalies<I: Iterator<Item = String>>(mut args: I) -> Result<()> {
+    let mut path = String::from(STDIN);
+    let mut detector = Detector::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--jump" => detector.jump = positive(&mut args, &arg)? as u32,
+            "--window" => detector.window = positive(&mut args, &arg)?,
+            "--outlier" => detector.outlier = positive(&mut args, &arg)? as f64,
+            _ => path = arg,
         }
     }
-     */
-
-    let numbers = io::BufReader::new(fs::File::open(file_name).unwrap())
-        .lines()
-        .map(|x| x.unwrap().parse::<u32>().unwrap())
-        .collect::<Vec<u32>>();
 
-    let counter = count_increments(numbers.as_slice());
-    println!("Found {} increments, expected {}", counter, solution1);
-
-    let window = make_sliding_window(numbers.as_slice());
-    let scounter = count_increments(&window);
-    println!(
-        "Found {} smooth increments, expected {}",
-        scounter, solution2
-    );
+    for anomaly in detector.detect(open_input(&path)?)? {
+        println!("{}", anomaly);
+    }
+    Ok(())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_count() {
-        let test_cases = [
-            (vec![0, 0, 1, 1, 2, 3, 3, 4, 5], 5, "1"),
-            (vec![0, 0, 0, 0, 0, 0, 0, 0, 0], 0, "2"),
-            (vec![1, 2, 1, 2, 1, 2, 1, 2], 4, "3"),
-        ];
-
-        for test_case in test_cases {
-            assert_eq!(
-                count_increments(test_case.0.as_slice()),
-                test_case.1,
-                "test #{:02}",
-                test_case.2
-            );
+fn main() {
+    let mut args = env::args().skip(1).peekable();
+    let result = match args.peek().map(String::as_str) {
+        Some("--stream") => stream(args.skip(1)),
+        Some("--anomalies") => anomalies(args.skip(1)),
+        _ => {
+            aoc_common::run(&Day01, "input_01.txt");
+            return;
         }
-    }
-
-    #[test]
-    fn test_smooth_count() {
-        let test_cases = [
-            (
-                vec![199, 200, 208, 210, 200, 207, 240, 269, 260, 263],
-                5,
-                "1",
-            ),
-            (vec![199], 0, "2"),
-            (vec![199, 200], 0, "3"),
-            (vec![199, 200, 208], 0, "4"),
-        ];
+    };
 
-        for test_case in test_cases {
-            let counter = make_sliding_window(test_case.0.as_slice());
-            assert_eq!(
-                count_increments(counter.as_slice()),
-                test_case.1,
-                "test #{:02}",
-                test_case.2
-            );
+    if let Err(why) = result {
+        eprintln!("Error: {}", why);
+        if let Error::Usage(_) = why {
+            eprintln!("{}", USAGE);
         }
+        process::exit(1);
     }
 }
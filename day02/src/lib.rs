@@ -0,0 +1,267 @@
+//! Day 2: Dive!, follows the course of the submarine
+
+pub mod safety;
+pub mod script;
+pub mod steering;
+pub mod trajectory;
+
+pub use safety::{Hazard, Limits, Violation};
+pub use script::{parse_script, read_script, Instruction};
+pub use steering::{Aim, Naive, SteeringModel, Submarine};
+pub use trajectory::Trajectory;
+
+use aoc_common::{count::overflow, Answer, Location, Result, Solver};
+use std::fmt;
+use std::io::BufRead;
+
+aoc_common::parse_error! {
+    /// Errors found parsing the planned course
+    pub enum ParseError {
+        /// A word that is neither a command nor a macro defined before
+        UnknownCommand(Location),
+        /// A command or repeat without the number of units or times
+        MissingUnits(Location),
+        /// Units that are not an integer number, or a negative number of repetitions
+        InvalidUnits(Location),
+        /// A brace or macro name out of place
+        UnexpectedToken(Location),
+        /// A block without its closing brace, located at the repeat or macro opening it
+        UnclosedBlock(Location),
+        /// A second macro with the same name
+        DuplicateMacro(Location),
+        /// A script expanding to more than `script::MAX_COMMANDS` commands
+        TooLong(Location),
+        /// A block inside more than `script::MAX_NESTING` others, located at the keyword opening it
+        TooDeep(Location),
+    }
+
+    fn fmt(&self, f) {
+        ParseError::UnknownCommand(loc) => {
+            write!(f, "{}: invalid command '{}'", loc, loc.token)
+        }
+        ParseError::MissingUnits(loc) => {
+            write!(f, "{}: missing units after '{}'", loc, loc.token)
+        }
+        ParseError::InvalidUnits(loc) => write!(f, "{}: invalid units '{}'", loc, loc.token),
+        ParseError::UnexpectedToken(loc) if loc.token.is_empty() => {
+            write!(f, "{}: unexpected end of script", loc)
+        }
+        ParseError::UnexpectedToken(loc) => {
+            write!(f, "{}: unexpected '{}'", loc, loc.token)
+        }
+        ParseError::UnclosedBlock(loc) => {
+            write!(f, "{}: block of '{}' is never closed", loc, loc.token)
+        }
+        ParseError::DuplicateMacro(loc) => {
+            write!(f, "{}: macro '{}' already defined", loc, loc.token)
+        }
+        ParseError::TooLong(loc) => {
+            write!(f, "{}: '{}' expands to too many commands", loc, loc.token)
+        }
+        ParseError::TooDeep(loc) => {
+            write!(f, "{}: block of '{}' is nested too deeply", loc, loc.token)
+        }
+    }
+}
+
+type ParseResult<T> = std::result::Result<T, ParseError>;
+
+/// Command of the planned course
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Forward(i32),
+    Up(i32),
+    Down(i32),
+    /// Moves backwards, keeping the aim
+    Back(i32),
+}
+
+impl fmt::Display for Command {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Command::Forward(x) => write!(f, "forward {}", x),
+            Command::Up(x) => write!(f, "up {}", x),
+            Command::Down(x) => write!(f, "down {}", x),
+            Command::Back(x) => write!(f, "back {}", x),
+        }
+    }
+}
+
+/// Reads the script of the course and expands it to the commands it runs
+pub fn parse_course<I: BufRead>(reader: I) -> ParseResult<Vec<Command>> {
+    Ok(read_script(reader)?
+        .into_iter()
+        .map(|instruction| instruction.command)
+        .collect())
+}
+
+/// Final horizontal position and depth when the commands move the submarine up and down
+pub fn parse_commands<I>(reader: I) -> Result<(i64, i64)>
+where
+    I: BufRead,
+{
+    let mut submarine = Submarine::new();
+    submarine.follow(&Naive, &parse_course(reader)?)?;
+    Ok((submarine.horizontal, submarine.depth))
+}
+
+/// Final horizontal position and depth when up and down change the aim of the submarine
+pub fn parse_commands_corrected<I>(reader: I) -> Result<(i64, i64)>
+where
+    I: BufRead,
+{
+    let mut submarine = Submarine::new();
+    submarine.follow(&Aim, &parse_course(reader)?)?;
+    Ok((submarine.horizontal, submarine.depth))
+}
+
+/// Final horizontal position multiplied by the depth
+fn product<M: SteeringModel>(model: &M, commands: &[Command]) -> Result<Answer> {
+    let mut submarine = Submarine::new();
+    submarine.follow(model, commands)?;
+    let product = submarine
+        .horizontal
+        .checked_mul(submarine.depth)
+        .ok_or_else(|| overflow("horizontal position times depth"))?;
+    Ok(product.into())
+}
+
+/// Solver of the day, both parts multiply the final horizontal position by the depth, part 1
+/// with the `Naive` steering model and part 2 with the `Aim` one
+pub struct Day02;
+
+impl Solver for Day02 {
+    const DAY: u32 = 2;
+
+    type Input = Vec<Command>;
+
+    fn parse<R: BufRead>(&self, reader: R) -> Result<Vec<Command>> {
+        Ok(parse_course(reader)?)
+    }
+
+    fn part1(&self, commands: &Vec<Command>) -> Result<Answer> {
+        product(&Naive, commands)
+    }
+
+    fn part2(&self, commands: &Vec<Command>) -> Result<Answer> {
+        product(&Aim, commands)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Error;
+    use std::io;
+
+    /// Parse error a course failed with
+    fn parse_error<T: fmt::Debug>(result: Result<T>) -> ParseError {
+        match result {
+            Err(Error::Parse(why)) => *why.downcast::<ParseError>().unwrap(),
+            other => panic!("expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parsing() {
+        let file = b"forward 1\ndown 2\nup 1" as &[u8];
+        let reader = io::BufReader::new(file);
+        let (horizontal, vertical) = parse_commands(reader).unwrap();
+
+        assert_eq!(horizontal, 1);
+        assert_eq!(vertical, 1);
+    }
+
+    #[test]
+    fn test_parsing_invalid_shift() {
+        let file = b"forward A\ndown 2\nup 1" as &[u8];
+        let reader = io::BufReader::new(file);
+        match parse_error(parse_commands(reader)) {
+            ParseError::InvalidUnits(loc) => assert_eq!(Location::new(1, 9, "A"), loc),
+            _ => panic!("expected invalid units"),
+        }
+    }
+
+    #[test]
+    fn test_parsing_invalid_command() {
+        let file = b"forward 1\ndown 2\n  foo 1" as &[u8];
+        let reader = io::BufReader::new(file);
+        match parse_error(parse_commands(reader)) {
+            ParseError::UnknownCommand(loc) => assert_eq!(Location::new(3, 3, "foo"), loc),
+            _ => panic!("expected an unknown command"),
+        }
+    }
+
+    #[test]
+    fn test_parsing_missing_units() {
+        let file = b"forward 1\ndown" as &[u8];
+        let reader = io::BufReader::new(file);
+        match parse_error(parse_commands(reader)) {
+            ParseError::MissingUnits(loc) => assert_eq!(Location::new(2, 1, "down"), loc),
+            _ => panic!("expected missing units"),
+        }
+    }
+
+    #[test]
+    fn test_parsing_corr() {
+        let file = b"forward 5\ndown 5\nforward 8\nup 3\ndown 8\nforward 2" as &[u8];
+        let reader = io::BufReader::new(file);
+        let (horizontal, vertical) = parse_commands_corrected(reader).unwrap();
+
+        assert_eq!(horizontal, 15);
+        assert_eq!(vertical, 60);
+    }
+
+    #[test]
+    fn test_parsing_corr_invalid_shift() {
+        let file = b"forward A\ndown 2\nup 1" as &[u8];
+        let reader = io::BufReader::new(file);
+        assert!(parse_commands_corrected(reader).is_err());
+    }
+
+    #[test]
+    fn test_parsing_corr_invalid_command() {
+        let file = b"foo 1\ndown 2\nup 1" as &[u8];
+        let reader = io::BufReader::new(file);
+        assert!(parse_commands_corrected(reader).is_err());
+    }
+
+    #[test]
+    fn test_parse_course() {
+        let file = b"forward 5\ndown 5\nup 3" as &[u8];
+        assert_eq!(
+            vec![Command::Forward(5), Command::Down(5), Command::Up(3)],
+            parse_course(file).unwrap()
+        );
+        assert_eq!(
+            vec![Command::Forward(5), Command::Back(2), Command::Back(2)],
+            parse_course(b"forward 5\n\n# twice\nrepeat 2 { back 2 }\n" as &[u8]).unwrap()
+        );
+        assert!(parse_course(b"forward 5\nrepeat 2 {" as &[u8]).is_err());
+    }
+
+    #[test]
+    fn test_large_course() {
+        let commands = parse_course(b"forward 100000\ndown 100000" as &[u8]).unwrap();
+        assert_eq!(
+            Answer::from(10_000_000_000i64),
+            Day02.part1(&commands).unwrap()
+        );
+        assert_eq!(Answer::from(0i64), Day02.part2(&commands).unwrap());
+
+        let commands = parse_course(b"down 2147483647\nforward 2147483647" as &[u8]).unwrap();
+        match Day02.part2(&commands) {
+            Err(Error::Overflow(_)) => (),
+            _ => panic!("expected an overflow"),
+        }
+
+        // the depth overflows moving forward, going back does not bring it back
+        let script =
+            b"repeat 3 { down 2147483647 }\nrepeat 2 { forward 2147483647 }\nback 2147483647";
+        let commands = parse_course(script as &[u8]).unwrap();
+        match Day02.part2(&commands) {
+            Err(Error::Overflow(_)) => (),
+            other => panic!("expected an overflow, got {:?}", other),
+        }
+    }
+}
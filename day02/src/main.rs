@@ -1,140 +1,84 @@
-use std::fs;
+use aoc_common::{open_input, Error, Result, STDIN};
+use day02::{
+    parse_course, read_script, Aim, Command, Day02, Limits, Naive, SteeringModel, Submarine,
+    Trajectory,
+};
+use std::env;
 use std::io;
-use std::io::BufRead;
-
-#[allow(dead_code)]
-fn parse_commands<I>(reader: I) -> (i32, i32)
-where
-    I: BufRead,
-{
-    let mut horizontal = 0;
-    let mut vertical = 0;
-    for (cnt, line) in reader.lines().enumerate() {
-        let lstr = match line {
-            Ok(lstr) => lstr,
-            Err(why) => panic!("Error reading line {}: {}", cnt, why),
-        };
-
-        let vec: Vec<&str> = lstr.as_str().split(" ").collect();
-        match vec[0] {
-            "forward" => horizontal += vec[1].parse::<i32>().unwrap(),
-            "up" => vertical -= vec[1].parse::<i32>().unwrap(),
-            "down" => vertical += vec[1].parse::<i32>().unwrap(),
-            _ => panic!("invalid command {} in line {}", vec[0], cnt),
+use std::process;
+
+const USAGE: &str = "usage: day02 [PATH|-] [--json] [--log FILTERS]
+       day02 --csv [--aim] [PATH|-]
+       day02 --check [--aim] [--crush N] [--clamp] [PATH|-]";
+
+/// Prints the state of the submarine after every command as CSV, with the `Aim` model if
+/// `--aim` is given and the `Naive` one otherwise
+fn csv<I: Iterator<Item = String>>(args: I) -> Result<()> {
+    let mut path = String::from(STDIN);
+    let mut model: &dyn SteeringModel = &Naive;
+    for arg in args {
+        match arg.as_str() {
+            "--aim" => model = &Aim,
+            _ => path = arg,
         }
     }
-    (horizontal, vertical)
-}
 
-#[allow(dead_code)]
-fn parse_commands_corrected<I>(reader: I) -> (i32, i32)
-where
-    I: BufRead,
-{
-    let mut horizontal = 0;
-    let mut vertical = 0;
-    let mut aim = 0;
-    for (cnt, line) in reader.lines().enumerate() {
-        let lstr = match line {
-            Ok(lstr) => lstr,
-            Err(why) => panic!("Error reading line {}: {}", cnt, why),
-        };
+    let commands = parse_course(open_input(&path)?)?;
+    Trajectory::record(model, &commands)?.write_csv(io::stdout().lock())?;
+    Ok(())
+}
 
-        let vec: Vec<&str> = lstr.as_str().split(" ").collect();
-        match vec[0] {
-            "forward" => {
-                let x = vec[1].parse::<i32>().unwrap();
-                horizontal += x;
-                vertical += aim * x;
-            }
-            "up" => aim -= vec[1].parse::<i32>().unwrap(),
-            "down" => aim += vec[1].parse::<i32>().unwrap(),
-            _ => panic!("invalid command {} in line {}", vec[0], cnt),
+/// Checks that the submarine stays between the surface and `--crush` and prints where it
+/// ends, with `--clamp` it stops at the limits instead of failing
+fn check<I: Iterator<Item = String>>(mut args: I) -> Result<()> {
+    let mut path = String::from(STDIN);
+    let mut limits = Limits::default();
+    let mut clamp = false;
+    let mut aim = false;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--aim" => aim = true,
+            "--clamp" => clamp = true,
+            "--crush" => match args.next().map(|n| n.parse::<i64>()) {
+                Some(Ok(n)) if n >= 0 => limits.crush_depth = Some(n),
+                _ => return Err(Error::Usage(String::from("invalid value for --crush"))),
+            },
+            _ => path = arg,
         }
     }
-    (horizontal, vertical)
-}
-
-fn main() {
-    let file_name = "input02.txt";
-    let f = match fs::File::open(file_name) {
-        Ok(f) => f,
-        Err(why) => panic!("Could not open file {}: error -> {}", file_name, why),
-    };
 
-    let reader = io::BufReader::new(f);
-    //let (horizontal, vertical) = parse_commands(reader);
-    // println!(
-    //     "Solution 1\nH: {}, V: {}, HxV: {}",
-    //     horizontal,
-    //     vertical,
-    //     horizontal * vertical
-    // );
-    let (horizontal, vertical) = parse_commands_corrected(reader);
+    let instructions = read_script(open_input(&path)?)?;
+    let commands: Vec<Command> = instructions.iter().map(|i| i.command).collect();
+    let mut submarine = Submarine::new();
+    match (clamp, aim) {
+        (false, false) => submarine = limits.check(&Naive, &instructions)?,
+        (false, true) => submarine = limits.check(&Aim, &instructions)?,
+        (true, false) => submarine.follow(&limits.clamp(Naive), &commands)?,
+        (true, true) => submarine.follow(&limits.clamp(Aim), &commands)?,
+    }
     println!(
-        "Solution 2\nH: {}, V: {}, HxV: {}",
-        horizontal,
-        vertical,
-        horizontal * vertical
+        "horizontal {} depth {}",
+        submarine.horizontal, submarine.depth
     );
+    Ok(())
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_parsing() {
-        let file = b"forward 1\ndown 2\nup 1" as &[u8];
-        let reader = io::BufReader::new(file);
-        let (horizontal, vertical) = parse_commands(reader);
-
-        assert_eq!(horizontal, 1);
-        assert_eq!(vertical, 1);
-    }
-
-    #[test]
-    #[should_panic]
-    fn test_parsing_invalid_shift() {
-        let file = b"forward A\ndown 2\nup 1" as &[u8];
-        let reader = io::BufReader::new(file);
-        let (_horizontal, _vertical) = parse_commands(reader);
-    }
-
-    #[test]
-    #[should_panic]
-    fn test_parsing_invalid_command() {
-        let file = b"foo 1\ndown 2\nup 1" as &[u8];
-        let reader = io::BufReader::new(file);
-        let (_horizontal, _vertical) = parse_commands(reader);
-    }
-
-    #[test]
-    fn test_parsing_corr() {
-        let file = b"forward 5\ndown 5\nforward 8\nup 3\ndown 8\nforward 2" as &[u8];
-        let reader = io::BufReader::new(file);
-        let (horizontal, vertical) = parse_commands_corrected(reader);
-
-        assert_eq!(horizontal, 15);
-        assert_eq!(vertical, 60);
-    }
-
-
-    #[test]
-    #[should_panic]
-    fn test_parsing_corr_invalid_shift() {
-        let file = b"forward A\ndown 2\nup 1" as &[u8];
-        let reader = io::BufReader::new(file);
-        let (_horizontal, _vertical) = parse_commands_corrected(reader);
-    }
+fn main() {
+    let mut args = env::args().skip(1).peekable();
+    let result = match args.peek().map(String::as_str) {
+        Some("--csv") => csv(args.skip(1)),
+        Some("--check") => check(args.skip(1)),
+        _ => {
+            aoc_common::run(&Day02, "input02.txt");
+            return;
+        }
+    };
 
-    #[test]
-    #[should_panic]
-    fn test_parsing_corr_invalid_command() {
-        let file = b"foo 1\ndown 2\nup 1" as &[u8];
-        let reader = io::BufReader::new(file);
-        let (_horizontal, _vertical) = parse_commands_corrected(reader);
+    if let Err(why) = result {
+        eprintln!("Error: {}", why);
+        if let Error::Usage(_) = why {
+            eprintln!("{}", USAGE);
+        }
+        process::exit(1);
     }
-
-
 }
@@ -0,0 +1,237 @@
+use crate::{Command, Instruction, SteeringModel, Submarine};
+use std::fmt;
+
+/// Limit of the depth the submarine went past
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Hazard {
+    /// Negative depth, the submarine flew out of the water
+    Surface,
+    /// Depth below the crush depth
+    Crush(i64),
+    /// Position, depth or aim past what the submarine can track, see `Submarine`
+    Overflow,
+}
+
+/// First command of a course taking the submarine past its limits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Violation {
+    pub hazard: Hazard,
+    pub instruction: Instruction,
+    /// Number of commands followed before the violating one
+    pub index: usize,
+    /// State after the violating command, or before it for an overflow
+    pub state: Submarine,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}: '{}' ",
+            self.instruction.line, self.instruction.command
+        )?;
+        match self.hazard {
+            Hazard::Surface => write!(f, "breaches the surface")?,
+            Hazard::Crush(limit) => write!(f, "goes below the crush depth {}", limit)?,
+            Hazard::Overflow => return write!(f, "takes the submarine out of range"),
+        }
+        write!(f, " at depth {}", self.state.depth)
+    }
+}
+
+impl std::error::Error for Violation {}
+
+impl From<Violation> for aoc_common::Error {
+    fn from(why: Violation) -> Self {
+        match why.hazard {
+            Hazard::Overflow => aoc_common::Error::Overflow(why.to_string()),
+            _ => aoc_common::Error::Constraint(why.to_string()),
+        }
+    }
+}
+
+/// Depths the submarine must stay within
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    /// The depth can't be negative
+    pub surface: bool,
+    /// Largest depth allowed, unlimited if None
+    pub crush_depth: Option<i64>,
+}
+
+impl Default for Limits {
+    fn default() -> Limits {
+        Limits {
+            surface: true,
+            crush_depth: None,
+        }
+    }
+}
+
+impl Limits {
+    /// Limit the submarine is past, if any
+    pub fn hazard(&self, submarine: &Submarine) -> Option<Hazard> {
+        match self.crush_depth {
+            _ if self.surface && submarine.depth < 0 => Some(Hazard::Surface),
+            Some(limit) if submarine.depth > limit => Some(Hazard::Crush(limit)),
+            _ => None,
+        }
+    }
+
+    /// Final state of the submarine following `instructions` from the surface as interpreted
+    /// by `model`, or the first instruction taking it past the limits or failing to steer
+    pub fn check<M: SteeringModel + ?Sized>(
+        &self,
+        model: &M,
+        instructions: &[Instruction],
+    ) -> Result<Submarine, Violation> {
+        let mut submarine = Submarine::new();
+        for (index, instruction) in instructions.iter().enumerate() {
+            let hazard = match model.steer(&mut submarine, instruction.command) {
+                Ok(()) => self.hazard(&submarine),
+                Err(_) => Some(Hazard::Overflow),
+            };
+            if let Some(hazard) = hazard {
+                return Err(Violation {
+                    hazard,
+                    instruction: *instruction,
+                    index,
+                    state: submarine,
+                });
+            }
+        }
+        Ok(submarine)
+    }
+
+    /// Steering model following `model` that keeps the depth within the limits
+    pub fn clamp<M: SteeringModel>(self, model: M) -> Clamped<M> {
+        Clamped {
+            model,
+            limits: self,
+        }
+    }
+}
+
+/// Steering model stopping the submarine at the surface and at the crush depth instead of
+/// going past them
+///
+/// Created with `Limits::clamp`, the aim is not changed.
+#[derive(Debug, Clone, Copy)]
+pub struct Clamped<M> {
+    model: M,
+    limits: Limits,
+}
+
+impl<M: SteeringModel> SteeringModel for Clamped<M> {
+    fn steer(&self, submarine: &mut Submarine, command: Command) -> aoc_common::Result<()> {
+        self.model.steer(submarine, command)?;
+        match self.limits.hazard(submarine) {
+            Some(Hazard::Surface) => submarine.depth = 0,
+            Some(Hazard::Crush(limit)) => submarine.depth = limit,
+            Some(Hazard::Overflow) | None => (),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_script, Aim, Naive};
+
+    #[test]
+    fn test_check() {
+        let course = parse_script("forward 5\ndown 5\nforward 8\nup 3\ndown 8\nforward 2").unwrap();
+        let limits = Limits::default();
+        assert_eq!(15, limits.check(&Naive, &course).unwrap().horizontal);
+        assert_eq!(60, limits.check(&Aim, &course).unwrap().depth);
+
+        let deep = Limits {
+            crush_depth: Some(50),
+            ..limits
+        };
+        let violation = deep.check(&Aim, &course).unwrap_err();
+        assert_eq!(Hazard::Crush(50), violation.hazard);
+        assert_eq!(5, violation.index);
+        assert_eq!(6, violation.instruction.line);
+        assert_eq!(
+            "line 6: 'forward 2' goes below the crush depth 50 at depth 60",
+            violation.to_string()
+        );
+        assert!(deep.check(&Naive, &course).is_ok());
+
+        match aoc_common::Error::from(violation) {
+            aoc_common::Error::Constraint(why) => assert!(why.starts_with("line 6: ")),
+            _ => panic!("expected a constraint error"),
+        }
+
+        let steep = parse_script("repeat 3 { down 2147483647 }\nforward 2147483647").unwrap();
+        let violation = limits.check(&Aim, &steep).unwrap_err();
+        assert_eq!((Hazard::Overflow, 3), (violation.hazard, violation.index));
+        assert_eq!(
+            "line 2: 'forward 2147483647' takes the submarine out of range",
+            violation.to_string()
+        );
+        match aoc_common::Error::from(violation) {
+            aoc_common::Error::Overflow(_) => (),
+            _ => panic!("expected an overflow"),
+        }
+    }
+
+    #[test]
+    fn test_check_surface() {
+        // with the aim model going up only breaches the surface moving forward
+        let course = parse_script("down 1\nforward 2\nup 3\nforward 2").unwrap();
+        let limits = Limits::default();
+        let violation = limits.check(&Naive, &course).unwrap_err();
+        assert_eq!(
+            (Hazard::Surface, 3),
+            (violation.hazard, violation.instruction.line)
+        );
+        let violation = limits.check(&Aim, &course).unwrap_err();
+        assert_eq!(
+            (Hazard::Surface, 4),
+            (violation.hazard, violation.instruction.line)
+        );
+        assert_eq!(
+            "line 4: 'forward 2' breaches the surface at depth -2",
+            violation.to_string()
+        );
+
+        let no_surface = Limits {
+            surface: false,
+            crush_depth: None,
+        };
+        assert_eq!(-2, no_surface.check(&Naive, &course).unwrap().depth);
+    }
+
+    #[test]
+    fn test_clamp() {
+        let limits = Limits {
+            surface: true,
+            crush_depth: Some(4),
+        };
+        let commands = [
+            Command::Up(2),
+            Command::Down(3),
+            Command::Down(3),
+            Command::Up(1),
+        ];
+        let mut submarine = Submarine::new();
+        submarine.follow(&limits.clamp(Naive), &commands).unwrap();
+        assert_eq!(3, submarine.depth);
+
+        let commands = [
+            Command::Down(3),
+            Command::Forward(2),
+            Command::Up(6),
+            Command::Forward(1),
+        ];
+        let mut submarine = Submarine::new();
+        submarine.follow(&limits.clamp(Aim), &commands).unwrap();
+        assert_eq!(
+            (3, 1, -3),
+            (submarine.horizontal, submarine.depth, submarine.aim)
+        );
+    }
+}
@@ -0,0 +1,347 @@
+//! Language of the navigation scripts
+//!
+//! Every line holds a command, `forward N`, `up N`, `down N` or `back N`, a block or a
+//! macro call. `#` starts a comment running to the end of the line and blank lines are
+//! ignored.
+//!
+//! ```text
+//! # two steps of a staircase
+//! macro step {
+//!     forward 2
+//!     down 1
+//! }
+//! repeat 3 { step }
+//! back 1
+//! ```
+//!
+//! `repeat K { ... }` runs the commands of the block K times and `macro NAME { ... }`
+//! defines the commands run by `NAME`, macros must be defined before they are called.
+
+use crate::{Command, ParseError, ParseResult};
+use aoc_common::Location;
+use std::collections::HashMap;
+use std::io::BufRead;
+
+/// Most commands a script can expand to, bounds the memory used by nested repeats
+pub const MAX_COMMANDS: usize = 1 << 24;
+
+/// Most blocks a script can nest, bounds the stack used to parse them
+pub const MAX_NESTING: usize = 256;
+
+/// Command of a script and the line where it is written
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Instruction {
+    pub command: Command,
+    /// Line of the command, inside the macro definition for commands run by a macro
+    pub line: usize,
+}
+
+/// Word or brace of a script
+#[derive(Clone)]
+struct Token<'a> {
+    text: &'a str,
+    loc: Location,
+}
+
+/// Splits the script in words and braces, skipping whitespace and comments
+fn tokenize(script: &str) -> Vec<Token<'_>> {
+    let mut tokens = vec![];
+    for (cnt, line) in script.lines().enumerate() {
+        let code = match line.find('#') {
+            Some(comment) => &line[..comment],
+            None => line,
+        };
+        let mut start = None;
+        for (i, c) in code.char_indices() {
+            if c.is_whitespace() || c == '{' || c == '}' {
+                if let Some(begin) = start.take() {
+                    tokens.push(token(cnt + 1, line, &code[begin..i]));
+                }
+                if !c.is_whitespace() {
+                    tokens.push(token(cnt + 1, line, &code[i..i + 1]));
+                }
+            } else if start.is_none() {
+                start = Some(i);
+            }
+        }
+        if let Some(begin) = start {
+            tokens.push(token(cnt + 1, line, &code[begin..]));
+        }
+    }
+    tokens
+}
+
+fn token<'a>(line_no: usize, line: &str, text: &'a str) -> Token<'a> {
+    Token {
+        text,
+        loc: Location::in_line(line_no, line, text),
+    }
+}
+
+fn is_keyword(word: &str) -> bool {
+    matches!(
+        word,
+        "forward" | "up" | "down" | "back" | "repeat" | "macro" | "{" | "}"
+    )
+}
+
+struct Parser<'a> {
+    tokens: Vec<Token<'a>>,
+    pos: usize,
+    macros: HashMap<&'a str, Vec<Instruction>>,
+    /// Blocks open at the current token
+    depth: usize,
+    /// End of the script, where errors about missing tokens are located
+    end: Location,
+}
+
+impl<'a> Parser<'a> {
+    fn next(&mut self) -> Option<Token<'a>> {
+        let token = self.tokens.get(self.pos).cloned();
+        self.pos += 1;
+        token
+    }
+
+    fn peek(&self) -> Option<&Token<'a>> {
+        self.tokens.get(self.pos)
+    }
+
+    /// Number following `keyword` in the same line, it can't be negative if `signed` is not set
+    fn units(&mut self, keyword: &Token, signed: bool) -> ParseResult<i32> {
+        match self.peek() {
+            Some(units) if units.loc.line == keyword.loc.line && !is_keyword(units.text) => {
+                let units = self.next().unwrap();
+                match units.text.parse::<i32>() {
+                    Ok(val) if signed || val >= 0 => Ok(val),
+                    _ => Err(ParseError::InvalidUnits(units.loc)),
+                }
+            }
+            _ => Err(ParseError::MissingUnits(keyword.loc.clone())),
+        }
+    }
+
+    /// Commands between `{` and `}`, `opener` is the keyword starting the block
+    fn block(&mut self, opener: &Token) -> ParseResult<Vec<Instruction>> {
+        match self.next() {
+            Some(brace) if brace.text == "{" => (),
+            Some(token) => return Err(ParseError::UnexpectedToken(token.loc)),
+            None => return Err(ParseError::UnexpectedToken(self.end.clone())),
+        }
+        if self.depth == MAX_NESTING {
+            return Err(ParseError::TooDeep(opener.loc.clone()));
+        }
+        self.depth += 1;
+        let instructions = self.statements()?;
+        self.depth -= 1;
+        match self.next() {
+            Some(brace) if brace.text == "}" => Ok(instructions),
+            _ => Err(ParseError::UnclosedBlock(opener.loc.clone())),
+        }
+    }
+
+    /// Statements until the end of the script or of the current block
+    fn statements(&mut self) -> ParseResult<Vec<Instruction>> {
+        let mut instructions = vec![];
+        while let Some(token) = self.peek() {
+            if token.text == "}" {
+                break;
+            }
+            let token = self.next().unwrap();
+            let command: fn(i32) -> Command = match token.text {
+                "forward" => Command::Forward,
+                "up" => Command::Up,
+                "down" => Command::Down,
+                "back" => Command::Back,
+                "repeat" => {
+                    let times = self.units(&token, false)?;
+                    let body = self.block(&token)?;
+                    if instructions.len() + body.len() * times as usize > MAX_COMMANDS {
+                        return Err(ParseError::TooLong(token.loc));
+                    }
+                    for _ in 0..times {
+                        instructions.extend_from_slice(&body);
+                    }
+                    continue;
+                }
+                "macro" => {
+                    let name = match self.next() {
+                        Some(name) if !is_keyword(name.text) && name.loc.line == token.loc.line => {
+                            name
+                        }
+                        Some(name) => return Err(ParseError::UnexpectedToken(name.loc)),
+                        None => return Err(ParseError::UnexpectedToken(self.end.clone())),
+                    };
+                    if self.macros.contains_key(name.text) {
+                        return Err(ParseError::DuplicateMacro(name.loc));
+                    }
+                    let body = self.block(&token)?;
+                    self.macros.insert(name.text, body);
+                    continue;
+                }
+                name => match self.macros.get(name) {
+                    Some(body) => {
+                        if instructions.len() + body.len() > MAX_COMMANDS {
+                            return Err(ParseError::TooLong(token.loc));
+                        }
+                        instructions.extend_from_slice(body);
+                        continue;
+                    }
+                    None => return Err(ParseError::UnknownCommand(token.loc)),
+                },
+            };
+            let units = self.units(&token, true)?;
+            if instructions.len() == MAX_COMMANDS {
+                return Err(ParseError::TooLong(token.loc));
+            }
+            instructions.push(Instruction {
+                command: command(units),
+                line: token.loc.line,
+            });
+        }
+        Ok(instructions)
+    }
+}
+
+/// Expands the script to the commands it runs, in order
+pub fn parse_script(script: &str) -> ParseResult<Vec<Instruction>> {
+    let lines = script.lines().count();
+    let mut parser = Parser {
+        tokens: tokenize(script),
+        pos: 0,
+        macros: HashMap::new(),
+        depth: 0,
+        end: Location::new(lines.max(1), 1, ""),
+    };
+    let instructions = parser.statements()?;
+    match parser.next() {
+        Some(token) => Err(ParseError::UnexpectedToken(token.loc)),
+        None => Ok(instructions),
+    }
+}
+
+/// Reads and expands the script of `reader`
+pub fn read_script<I: BufRead>(mut reader: I) -> ParseResult<Vec<Instruction>> {
+    let mut script = String::new();
+    reader.read_to_string(&mut script)?;
+    parse_script(&script)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commands(script: &str) -> Vec<Command> {
+        parse_script(script)
+            .unwrap()
+            .into_iter()
+            .map(|i| i.command)
+            .collect()
+    }
+
+    #[test]
+    fn test_tokenize() {
+        let tokens = tokenize("repeat 2{down 1}  # comment {\n\n  back 3");
+        let texts: Vec<&str> = tokens.iter().map(|t| t.text).collect();
+        assert_eq!(
+            vec!["repeat", "2", "{", "down", "1", "}", "back", "3"],
+            texts
+        );
+        assert_eq!(Location::new(1, 9, "{"), tokens[2].loc);
+        assert_eq!(Location::new(3, 3, "back"), tokens[6].loc);
+    }
+
+    #[test]
+    fn test_parse_script() {
+        let script = "# staircase
+macro step {
+    forward 2
+    down 1   # one down
+}
+
+repeat 2 { step }
+back 1
+";
+        assert_eq!(
+            vec![
+                Command::Forward(2),
+                Command::Down(1),
+                Command::Forward(2),
+                Command::Down(1),
+                Command::Back(1),
+            ],
+            commands(script)
+        );
+
+        let lines: Vec<usize> = parse_script(script)
+            .unwrap()
+            .iter()
+            .map(|i| i.line)
+            .collect();
+        assert_eq!(vec![3, 4, 3, 4, 8], lines);
+
+        assert_eq!(
+            vec![Command::Up(1); 6],
+            commands("repeat 2 {\n repeat 3 { up 1 }\n}")
+        );
+        assert!(commands("repeat 0 { up 1 }\n# nothing\n").is_empty());
+    }
+
+    #[test]
+    fn test_parse_script_errors() {
+        let cases = [
+            (
+                "forward 1\nrepeat 2 { up 1",
+                "unclosed",
+                Location::new(2, 1, "repeat"),
+            ),
+            ("up 1\n}", "unexpected", Location::new(2, 1, "}")),
+            ("repeat x { up 1 }", "units", Location::new(1, 8, "x")),
+            ("repeat -1 { up 1 }", "units", Location::new(1, 8, "-1")),
+            ("repeat 2 up 1", "unexpected", Location::new(1, 10, "up")),
+            ("zigzag", "unknown", Location::new(1, 1, "zigzag")),
+            ("macro m { m }", "unknown", Location::new(1, 11, "m")),
+            ("forward 1 2", "unknown", Location::new(1, 11, "2")),
+            ("up\n3", "missing", Location::new(1, 1, "up")),
+            (
+                "macro m { up 1 }\nmacro m { up 2 }",
+                "duplicate",
+                Location::new(2, 7, "m"),
+            ),
+            ("macro\nm { up 1 }", "unexpected", Location::new(2, 1, "m")),
+        ];
+        for (script, kind, expected) in cases {
+            let loc = match (kind, parse_script(script)) {
+                ("unclosed", Err(ParseError::UnclosedBlock(loc))) => loc,
+                ("unexpected", Err(ParseError::UnexpectedToken(loc))) => loc,
+                ("units", Err(ParseError::InvalidUnits(loc))) => loc,
+                ("unknown", Err(ParseError::UnknownCommand(loc))) => loc,
+                ("missing", Err(ParseError::MissingUnits(loc))) => loc,
+                ("duplicate", Err(ParseError::DuplicateMacro(loc))) => loc,
+                (_, result) => panic!("expected {} for {:?}, got {:?}", kind, script, result),
+            };
+            assert_eq!(expected, loc, "{:?}", script);
+        }
+    }
+
+    #[test]
+    fn test_parse_script_too_long() {
+        match parse_script("repeat 100000 { repeat 100000 { forward 1 } }") {
+            Err(ParseError::TooLong(loc)) => assert_eq!(Location::new(1, 1, "repeat"), loc),
+            _ => panic!("expected a script too long"),
+        }
+    }
+
+    #[test]
+    fn test_parse_script_too_deep() {
+        let nested = |depth| "repeat 1 {\n".repeat(depth) + "forward 1\n" + &"}\n".repeat(depth);
+        assert_eq!(vec![Command::Forward(1)], commands(&nested(MAX_NESTING)));
+        match parse_script(&nested(MAX_NESTING + 1)) {
+            Err(ParseError::TooDeep(loc)) => {
+                assert_eq!(Location::new(MAX_NESTING + 1, 1, "repeat"), loc)
+            }
+            other => panic!("expected a script too deep, got {:?}", other),
+        }
+        // far deeper than the stack could take
+        assert!(parse_script(&nested(200_000)).is_err());
+    }
+}
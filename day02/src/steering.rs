@@ -0,0 +1,217 @@
+use crate::Command;
+use aoc_common::count::overflow;
+use aoc_common::Result;
+
+/// Position of the submarine
+///
+/// Wider than the units of the commands, the steering models fail with `Error::Overflow` on
+/// the courses that go past it anyway.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Submarine {
+    pub horizontal: i64,
+    pub depth: i64,
+    /// Depth gained per unit moved forward, only used by some steering models
+    pub aim: i64,
+}
+
+impl Submarine {
+    /// Submarine at the surface, where the course starts
+    pub fn new() -> Submarine {
+        Submarine::default()
+    }
+
+    /// Moves the submarine following `commands` as interpreted by `model`, stopping at the
+    /// first one that fails
+    pub fn follow<M: SteeringModel + ?Sized>(
+        &mut self,
+        model: &M,
+        commands: &[Command],
+    ) -> Result<()> {
+        for command in commands {
+            model.steer(self, *command)?;
+        }
+        Ok(())
+    }
+}
+
+/// Sum of two coordinates, failing if it doesn't fit
+fn add(a: i64, b: i64) -> Result<i64> {
+    a.checked_add(b)
+        .ok_or_else(|| overflow("position of the submarine"))
+}
+
+/// Product of two coordinates, failing if it doesn't fit
+fn mul(a: i64, b: i64) -> Result<i64> {
+    a.checked_mul(b)
+        .ok_or_else(|| overflow("position of the submarine"))
+}
+
+/// Interpretation of the commands of the course
+///
+/// Closures taking the submarine and the command are steering models too:
+///
+/// ```
+/// use day02::{Command, Submarine};
+///
+/// // up and down move twice as much
+/// let double = |sub: &mut Submarine, command: Command| -> aoc_common::Result<()> {
+///     match command {
+///         Command::Forward(x) => sub.horizontal += i64::from(x),
+///         Command::Back(x) => sub.horizontal -= i64::from(x),
+///         Command::Up(x) => sub.depth -= 2 * i64::from(x),
+///         Command::Down(x) => sub.depth += 2 * i64::from(x),
+///     }
+///     Ok(())
+/// };
+/// let mut submarine = Submarine::new();
+/// submarine.follow(&double, &[Command::Down(3), Command::Forward(2)])?;
+/// assert_eq!(6, submarine.depth);
+/// # Ok::<(), aoc_common::Error>(())
+/// ```
+pub trait SteeringModel {
+    /// Moves the submarine as `command` says, leaving it where it was if that fails
+    fn steer(&self, submarine: &mut Submarine, command: Command) -> Result<()>;
+}
+
+impl<F: Fn(&mut Submarine, Command) -> Result<()>> SteeringModel for F {
+    fn steer(&self, submarine: &mut Submarine, command: Command) -> Result<()> {
+        self(submarine, command)
+    }
+}
+
+/// Up and down change the depth directly
+#[derive(Debug, Clone, Copy)]
+pub struct Naive;
+
+impl SteeringModel for Naive {
+    fn steer(&self, submarine: &mut Submarine, command: Command) -> Result<()> {
+        match command {
+            Command::Forward(x) => submarine.horizontal = add(submarine.horizontal, x.into())?,
+            Command::Back(x) => submarine.horizontal = add(submarine.horizontal, -i64::from(x))?,
+            Command::Up(x) => submarine.depth = add(submarine.depth, -i64::from(x))?,
+            Command::Down(x) => submarine.depth = add(submarine.depth, x.into())?,
+        }
+        Ok(())
+    }
+}
+
+/// Up and down change the aim, the depth changes moving forward
+#[derive(Debug, Clone, Copy)]
+pub struct Aim;
+
+impl Aim {
+    /// Moves `x` units forward following the aim, backwards if negative
+    fn advance(submarine: &mut Submarine, x: i64) -> Result<()> {
+        let depth = add(submarine.depth, mul(submarine.aim, x)?)?;
+        submarine.horizontal = add(submarine.horizontal, x)?;
+        submarine.depth = depth;
+        Ok(())
+    }
+}
+
+impl SteeringModel for Aim {
+    fn steer(&self, submarine: &mut Submarine, command: Command) -> Result<()> {
+        match command {
+            Command::Forward(x) => Aim::advance(submarine, x.into())?,
+            Command::Back(x) => Aim::advance(submarine, -i64::from(x))?,
+            Command::Up(x) => submarine.aim = add(submarine.aim, -i64::from(x))?,
+            Command::Down(x) => submarine.aim = add(submarine.aim, x.into())?,
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Error;
+
+    const COURSE: [Command; 6] = [
+        Command::Forward(5),
+        Command::Down(5),
+        Command::Forward(8),
+        Command::Up(3),
+        Command::Down(8),
+        Command::Forward(2),
+    ];
+
+    #[test]
+    fn test_naive() {
+        let mut submarine = Submarine::new();
+        submarine.follow(&Naive, &COURSE).unwrap();
+        assert_eq!(
+            Submarine {
+                horizontal: 15,
+                depth: 10,
+                aim: 0
+            },
+            submarine
+        );
+    }
+
+    #[test]
+    fn test_aim() {
+        let mut submarine = Submarine::new();
+        submarine.follow(&Aim, &COURSE).unwrap();
+        assert_eq!(
+            Submarine {
+                horizontal: 15,
+                depth: 60,
+                aim: 10
+            },
+            submarine
+        );
+    }
+
+    #[test]
+    fn test_dyn_model() {
+        let models: [&dyn SteeringModel; 2] = [&Naive, &Aim];
+        let depths: Vec<i64> = models
+            .iter()
+            .map(|model| {
+                let mut submarine = Submarine::new();
+                submarine.follow(*model, &COURSE).unwrap();
+                submarine.depth
+            })
+            .collect();
+        assert_eq!(vec![10, 60], depths);
+    }
+
+    #[test]
+    fn test_back() {
+        let mut naive = Submarine::new();
+        naive
+            .follow(&Naive, &[Command::Down(2), Command::Back(3)])
+            .unwrap();
+        assert_eq!((-3, 2), (naive.horizontal, naive.depth));
+
+        // going back retraces the forward move
+        let mut aim = Submarine::new();
+        aim.follow(
+            &Aim,
+            &[Command::Down(2), Command::Forward(5), Command::Back(3)],
+        )
+        .unwrap();
+        assert_eq!((2, 4, 2), (aim.horizontal, aim.depth, aim.aim));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let steep = [Command::Down(i32::MAX); 3];
+        let mut submarine = Submarine::new();
+        submarine.follow(&Aim, &steep).unwrap();
+        let before = submarine;
+        match Aim.steer(&mut submarine, Command::Forward(i32::MAX)) {
+            Err(Error::Overflow(_)) => (),
+            other => panic!("expected an overflow, got {:?}", other),
+        }
+        assert_eq!(before, submarine);
+
+        let mut submarine = Submarine {
+            horizontal: i64::MIN + 1,
+            ..Submarine::new()
+        };
+        assert!(submarine.follow(&Naive, &[Command::Back(2)]).is_err());
+        assert_eq!(i64::MIN + 1, submarine.horizontal);
+    }
+}
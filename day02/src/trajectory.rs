@@ -0,0 +1,135 @@
+use crate::{Command, SteeringModel, Submarine};
+use aoc_common::Result;
+use std::io::{self, Write};
+
+/// States of the submarine along a course, from the start to the end
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trajectory {
+    /// The state at the start followed by the state after every command
+    states: Vec<Submarine>,
+}
+
+impl Trajectory {
+    /// Follows `commands` from the surface as interpreted by `model`, recording every state,
+    /// fails if a command does
+    pub fn record<M: SteeringModel + ?Sized>(
+        model: &M,
+        commands: &[Command],
+    ) -> Result<Trajectory> {
+        let mut submarine = Submarine::new();
+        let mut states = Vec::with_capacity(commands.len() + 1);
+        states.push(submarine);
+        for command in commands {
+            model.steer(&mut submarine, *command)?;
+            states.push(submarine);
+        }
+        Ok(Trajectory { states })
+    }
+
+    /// State after the first `n` commands, the start for 0 and None past the end of the course
+    pub fn after(&self, n: usize) -> Option<&Submarine> {
+        self.states.get(n)
+    }
+
+    /// Every state in order, the first one is the start
+    pub fn states(&self) -> &[Submarine] {
+        &self.states
+    }
+
+    /// State at the end of the course
+    pub fn last(&self) -> &Submarine {
+        self.states.last().unwrap()
+    }
+
+    /// Number of commands followed to reach the deepest state and that state, the first one
+    /// when the depth is reached several times
+    pub fn deepest(&self) -> (usize, &Submarine) {
+        self.states
+            .iter()
+            .enumerate()
+            .rev()
+            .max_by_key(|(_, state)| state.depth)
+            .unwrap()
+    }
+
+    /// Largest depth reached, 0 if the submarine never dives
+    pub fn max_depth(&self) -> i64 {
+        self.deepest().1.depth
+    }
+
+    /// Writes the states as CSV with a header, one line per state numbered by the commands
+    /// followed to reach it
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "step,horizontal,depth,aim")?;
+        for (step, state) in self.states.iter().enumerate() {
+            writeln!(
+                writer,
+                "{},{},{},{}",
+                step, state.horizontal, state.depth, state.aim
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Aim, Naive};
+
+    const COURSE: [Command; 6] = [
+        Command::Forward(5),
+        Command::Down(5),
+        Command::Forward(8),
+        Command::Up(3),
+        Command::Down(8),
+        Command::Forward(2),
+    ];
+
+    #[test]
+    fn test_record() {
+        let trajectory = Trajectory::record(&Aim, &COURSE).unwrap();
+        assert_eq!(7, trajectory.states().len());
+        assert_eq!(Some(&Submarine::new()), trajectory.after(0));
+        assert_eq!(
+            Some(&Submarine {
+                horizontal: 13,
+                depth: 40,
+                aim: 5
+            }),
+            trajectory.after(3)
+        );
+        assert_eq!(None, trajectory.after(7));
+
+        let mut submarine = Submarine::new();
+        submarine.follow(&Aim, &COURSE).unwrap();
+        assert_eq!(&submarine, trajectory.last());
+
+        let empty = Trajectory::record(&Naive, &[]).unwrap();
+        assert_eq!(&Submarine::new(), empty.last());
+        assert_eq!(0, empty.max_depth());
+    }
+
+    #[test]
+    fn test_deepest() {
+        let trajectory = Trajectory::record(&Naive, &COURSE).unwrap();
+        assert_eq!(10, trajectory.max_depth());
+        assert_eq!(5, trajectory.deepest().0);
+
+        // the first time the depth is reached
+        let trajectory =
+            Trajectory::record(&Naive, &[Command::Down(2), Command::Forward(1)]).unwrap();
+        assert_eq!(1, trajectory.deepest().0);
+    }
+
+    #[test]
+    fn test_write_csv() {
+        let trajectory = Trajectory::record(&Aim, &COURSE[..2]).unwrap();
+        let mut csv = vec![];
+        trajectory.write_csv(&mut csv).unwrap();
+        assert_eq!(
+            "step,horizontal,depth,aim\n0,0,0,0\n1,5,0,0\n2,5,0,5\n",
+            String::from_utf8(csv).unwrap()
+        );
+    }
+}
@@ -0,0 +1,104 @@
+//! Time of the life support ratings of a large random report for each way of computing them
+//!
+//! `cargo bench -p day03 --bench ratings -- [--lines N] [--width N] [--runs N]`, a million
+//! 12 bit words by default.
+
+use aoc_common::profile::{measure, Measure};
+use aoc_common::{CountingAlloc, Result, Rng};
+use day03::{filter_by_count, Criterion, ScanOrder, Trie, Word};
+use std::env;
+
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+/// Random words, the same ones on every run
+fn report(lines: usize, width: usize) -> Vec<Word> {
+    let mut rng = Rng::new(0);
+    (0..lines)
+        .map(|_| {
+            let mut word = Word::zeros(width);
+            for pos in 0..width {
+                word.set(pos, rng.next_u64() & 1 == 1);
+            }
+            word
+        })
+        .collect()
+}
+
+/// Ratings recounting the words left every round with `filter_by_count`
+fn recount(width: usize, words: &[Word]) -> (Word, Word) {
+    let mut ratings = vec![];
+    for keep_most in [true, false] {
+        let mut vec = words.to_vec();
+        for pos in (0..width).rev() {
+            let (most, least) = filter_by_count(pos, width, vec.clone());
+            // every word left has the same bit when one side is empty, they are all kept
+            let kept = if keep_most { most } else { least };
+            if !kept.is_empty() {
+                vec = kept;
+            }
+            if vec.len() == 1 {
+                break;
+            }
+        }
+        ratings.push(vec.swap_remove(0));
+    }
+    (ratings.swap_remove(0), ratings.swap_remove(0))
+}
+
+fn scan(words: &[Word]) -> (Word, Word) {
+    (
+        Criterion::OXYGEN.filter(words).unwrap().survivor,
+        Criterion::CO2.filter(words).unwrap().survivor,
+    )
+}
+
+fn trie(words: &[Word]) -> (Word, Word) {
+    let trie = Trie::from_words(words, ScanOrder::MostSignificantFirst).unwrap();
+    (
+        trie.filter(&Criterion::OXYGEN).unwrap().survivor,
+        trie.filter(&Criterion::CO2).unwrap().survivor,
+    )
+}
+
+fn row(name: &str, measure: &Measure) -> String {
+    format!(
+        "{:<16}  {:>10.1}  {:>10.1}  {:>10.1}  {:>12}",
+        name,
+        measure.stats.min.as_secs_f64() * 1000.0,
+        measure.stats.median.as_secs_f64() * 1000.0,
+        measure.stats.max.as_secs_f64() * 1000.0,
+        measure.peak_bytes
+    )
+}
+
+fn main() -> Result<()> {
+    let (mut lines, mut width, mut runs) = (1_000_000, 12, 3);
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let val = match arg.as_str() {
+            "--lines" => &mut lines,
+            "--width" => &mut width,
+            "--runs" => &mut runs,
+            // flags added by cargo bench
+            _ => continue,
+        };
+        *val = args.next().and_then(|n| n.parse().ok()).unwrap_or(*val);
+    }
+
+    let words = report(lines, width);
+    println!("{} words of {} bits, {} runs", lines, width, runs);
+    println!(
+        "{:<16}  {:>10}  {:>10}  {:>10}  {:>12}",
+        "method", "min ms", "median ms", "max ms", "peak bytes"
+    );
+    let (expected, trie_measure) = measure(runs, || Ok(trie(&words)))?;
+    let (scanned, scan_measure) = measure(runs, || Ok(scan(&words)))?;
+    let (recounted, recount_measure) = measure(runs, || Ok(recount(width, &words)))?;
+    assert_eq!(expected, scanned);
+    assert_eq!(expected, recounted);
+    println!("{}", row("filter_by_count", &recount_measure));
+    println!("{}", row("Criterion", &scan_measure));
+    println!("{}", row("Trie", &trie_measure));
+    Ok(())
+}
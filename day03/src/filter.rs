@@ -0,0 +1,217 @@
+use crate::Word;
+
+/// Which value of the scanned bit keeps a word
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Keep {
+    MostCommon,
+    LeastCommon,
+}
+
+/// Value of the bit kept when there are as many ones as zeros
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TieBreak {
+    One,
+    Zero,
+}
+
+/// Order the bit positions are scanned in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScanOrder {
+    MostSignificantFirst,
+    LeastSignificantFirst,
+}
+
+/// Rule filtering the words of a report bit by bit until a single one is left
+///
+/// New ratings are defined by the rule alone, e.g. the word left keeping the most common
+/// bits from the right, and zeros on ties:
+///
+/// ```
+/// use day03::{Criterion, Keep, ScanOrder, TieBreak, Word};
+///
+/// let criterion = Criterion {
+///     keep: Keep::MostCommon,
+///     tie: TieBreak::Zero,
+///     order: ScanOrder::LeastSignificantFirst,
+/// };
+/// let words: Vec<Word> = [0b001, 0b011, 0b110]
+///     .iter()
+///     .map(|val| Word::from_u64(*val, 3))
+///     .collect();
+/// let filtered = criterion.filter(&words).unwrap();
+/// assert_eq!(Some(0b001), filtered.survivor.to_u64());
+/// assert_eq!(vec![2, 1], filtered.remaining());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Criterion {
+    pub keep: Keep,
+    pub tie: TieBreak,
+    pub order: ScanOrder,
+}
+
+/// Round of a filter, scanning one bit position
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Round {
+    /// Position of the bit, 0 for the least significant one
+    pub pos: usize,
+    /// Value of the bit of the words kept
+    pub bit: bool,
+    /// Number of words left after the round
+    pub remaining: usize,
+}
+
+/// Word left by a filter and the rounds it took
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Filtered {
+    pub survivor: Word,
+    pub trace: Vec<Round>,
+}
+
+impl Filtered {
+    /// Number of words left after every round
+    pub fn remaining(&self) -> Vec<usize> {
+        self.trace.iter().map(|round| round.remaining).collect()
+    }
+}
+
+impl Criterion {
+    /// Keeps the most common bits, ones on ties, from the left
+    pub const OXYGEN: Criterion = Criterion {
+        keep: Keep::MostCommon,
+        tie: TieBreak::One,
+        order: ScanOrder::MostSignificantFirst,
+    };
+
+    /// Keeps the least common bits, zeros on ties, from the left
+    pub const CO2: Criterion = Criterion {
+        keep: Keep::LeastCommon,
+        tie: TieBreak::Zero,
+        order: ScanOrder::MostSignificantFirst,
+    };
+
+    /// Value of the bit kept among `ones` ones and `zeros` zeros
+    ///
+    /// A value no word has is never kept, when all the words share the bit they all stay.
+    pub fn bit(&self, ones: usize, zeros: usize) -> bool {
+        if ones == 0 || zeros == 0 {
+            return ones > 0;
+        }
+        match (ones.cmp(&zeros), self.keep) {
+            (std::cmp::Ordering::Equal, _) => self.tie == TieBreak::One,
+            (order, Keep::MostCommon) => order.is_gt(),
+            (order, Keep::LeastCommon) => order.is_lt(),
+        }
+    }
+
+    /// Filters `words` until one is left, or all the bits are scanned, then the first of
+    /// those left survives; None if there are no words
+    ///
+    /// # Panics
+    ///
+    /// If the words have different widths
+    pub fn filter(&self, words: &[Word]) -> Option<Filtered> {
+        let width = words.first()?.len();
+        assert!(
+            words.iter().all(|word| word.len() == width),
+            "words of different widths"
+        );
+        let positions: Box<dyn Iterator<Item = usize>> = match self.order {
+            ScanOrder::MostSignificantFirst => Box::new((0..width).rev()),
+            ScanOrder::LeastSignificantFirst => Box::new(0..width),
+        };
+
+        let mut candidates: Vec<&Word> = words.iter().collect();
+        let mut trace = vec![];
+        for pos in positions {
+            if candidates.len() == 1 {
+                break;
+            }
+            let ones = candidates.iter().filter(|word| word.bit(pos)).count();
+            let bit = self.bit(ones, candidates.len() - ones);
+            candidates.retain(|word| word.bit(pos) == bit);
+            trace.push(Round {
+                pos,
+                bit,
+                remaining: candidates.len(),
+            });
+        }
+        Some(Filtered {
+            survivor: candidates[0].clone(),
+            trace,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn words(len: usize, vals: &[u64]) -> Vec<Word> {
+        vals.iter().map(|val| Word::from_u64(*val, len)).collect()
+    }
+
+    const REPORT: [u64; 12] = [
+        0b00100, 0b11110, 0b10110, 0b10111, 0b10101, 0b01111, 0b00111, 0b11100, 0b10000, 0b11001,
+        0b00010, 0b01010,
+    ];
+
+    #[test]
+    fn test_bit() {
+        assert!(Criterion::OXYGEN.bit(3, 2));
+        assert!(Criterion::OXYGEN.bit(2, 2));
+        assert!(!Criterion::CO2.bit(3, 2));
+        assert!(!Criterion::CO2.bit(2, 2));
+        assert!(Criterion::CO2.bit(2, 3));
+        // the only value present is kept
+        assert!(Criterion::CO2.bit(4, 0));
+        assert!(!Criterion::OXYGEN.bit(0, 4));
+    }
+
+    #[test]
+    fn test_filter() {
+        let report = words(5, &REPORT);
+        let oxygen = Criterion::OXYGEN.filter(&report).unwrap();
+        assert_eq!(Some(23), oxygen.survivor.to_u64());
+        assert_eq!(vec![7, 4, 3, 2, 1], oxygen.remaining());
+        assert_eq!(
+            Round {
+                pos: 4,
+                bit: true,
+                remaining: 7
+            },
+            oxygen.trace[0]
+        );
+
+        let co2 = Criterion::CO2.filter(&report).unwrap();
+        assert_eq!(Some(10), co2.survivor.to_u64());
+        assert_eq!(vec![5, 2, 1], co2.remaining());
+        assert_eq!(
+            vec![4, 3, 2],
+            co2.trace.iter().map(|r| r.pos).collect::<Vec<_>>()
+        );
+
+        assert_eq!(None, Criterion::OXYGEN.filter(&[]));
+    }
+
+    #[test]
+    fn test_filter_duplicates() {
+        // every bit is scanned and the first word left survives
+        let filtered = Criterion::CO2
+            .filter(&words(3, &[0b101, 0b101, 0b111]))
+            .unwrap();
+        assert_eq!(Some(0b111), filtered.survivor.to_u64());
+        let filtered = Criterion::OXYGEN
+            .filter(&words(3, &[0b101, 0b101, 0b111]))
+            .unwrap();
+        assert_eq!(Some(0b101), filtered.survivor.to_u64());
+        assert_eq!(vec![3, 2, 2], filtered.remaining());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_filter_different_widths() {
+        let mut report = words(5, &REPORT);
+        report.push(Word::zeros(6));
+        Criterion::OXYGEN.filter(&report);
+    }
+}
@@ -0,0 +1,481 @@
+//! Day 3: Binary Diagnostic, finds the power consumption and life support rating of the
+//! submarine from the diagnostic report
+
+pub mod filter;
+pub mod trie;
+pub mod word;
+
+pub use filter::{Criterion, Filtered, Keep, Round, ScanOrder, TieBreak};
+pub use trie::Trie;
+pub use word::Word;
+
+use aoc_common::count::overflow;
+use aoc_common::{Answer, Count, Error, Location, Result, Solver};
+use std::io::BufRead;
+
+aoc_common::parse_error! {
+    /// Errors found parsing the diagnostic report
+    pub enum ParseError {
+        /// A character other than '0' or '1'
+        InvalidDigit(Location),
+        /// A word with a different length than the first one
+        WordLength {
+            loc: Location,
+            expected: usize,
+        },
+    }
+
+    fn fmt(&self, f) {
+        ParseError::InvalidDigit(loc) => {
+            write!(f, "{}: invalid binary digit '{}'", loc, loc.token)
+        }
+        ParseError::WordLength { loc, expected } => write!(
+            f,
+            "{}: word '{}' has {} bits, expected {}",
+            loc,
+            loc.token,
+            loc.token.len(),
+            expected
+        ),
+    }
+}
+
+type ParseResult<T> = std::result::Result<T, ParseError>;
+
+/// Number of ones and zeros found in each bit position, the least significant bit first
+#[derive(Debug, Default)]
+pub struct BinaryNumbers {
+    ones: Vec<u32>,
+    zeros: Vec<u32>,
+    word_len: usize,
+}
+
+impl BinaryNumbers {
+    pub fn new() -> BinaryNumbers {
+        BinaryNumbers {
+            ones: vec![],
+            zeros: vec![],
+            word_len: 0,
+        }
+    }
+
+    /// Gamma and epsilon rates, built from the most and least common bits
+    pub fn rates(&self) -> (Word, Word) {
+        let mut gamma = Word::zeros(self.word_len);
+        for (i, (ones, zeros)) in self.ones.iter().zip(self.zeros.iter()).enumerate() {
+            gamma.set(i, ones >= zeros);
+        }
+        let epsilon = gamma.complement();
+        (gamma, epsilon)
+    }
+}
+
+/// Reads one binary word per line, of any width, returns the word length and the words
+pub fn parse_strings<I: BufRead>(reader: I) -> ParseResult<(usize, Vec<Word>)> {
+    let mut number_of_bits = 0;
+    let mut vec: Vec<Word> = Vec::new();
+
+    for (cnt, line) in reader.lines().enumerate() {
+        let lstr = line?;
+        if let Some((column, c)) = lstr.char_indices().find(|(_, c)| *c != '0' && *c != '1') {
+            let digit = &lstr[column..column + c.len_utf8()];
+            return Err(ParseError::InvalidDigit(Location::in_line(
+                cnt + 1,
+                &lstr,
+                digit,
+            )));
+        }
+        if cnt == 0 {
+            number_of_bits = lstr.len();
+        } else if number_of_bits != lstr.len() {
+            return Err(ParseError::WordLength {
+                loc: Location::new(cnt + 1, 1, &lstr),
+                expected: number_of_bits,
+            });
+        }
+        vec.push(Word::from_binary(&lstr).unwrap());
+    }
+
+    Ok((number_of_bits, vec))
+}
+
+/// Counts the ones and zeros of each bit position of the words
+pub fn count_numbers_in_column(number_of_bits: usize, vec: Vec<Word>) -> BinaryNumbers {
+    let mut numbers = BinaryNumbers::new();
+    numbers.word_len = number_of_bits;
+    numbers.ones.resize(number_of_bits, 0);
+    numbers.zeros.resize(number_of_bits, 0);
+    for val in vec {
+        for i in 0..numbers.word_len {
+            if val.bit(i) {
+                numbers.ones[i] += 1;
+            } else {
+                numbers.zeros[i] += 1;
+            }
+        }
+    }
+
+    numbers
+}
+
+/// Splits the words in those with the most common and the least common value at bit `pos`
+///
+/// Panics if `pos` is not smaller than `number_of_bits`
+pub fn filter_by_count(
+    pos: usize,
+    number_of_bits: usize,
+    vec: Vec<Word>,
+) -> (Vec<Word>, Vec<Word>) {
+    let mut vec_most_common: Vec<Word> = vec![];
+    let mut vec_least_common: Vec<Word> = vec![];
+
+    if pos > number_of_bits - 1 {
+        panic!("search position out of bounds");
+    }
+
+    let numbers = count_numbers_in_column(number_of_bits, vec.clone());
+
+    let most_common_value = numbers.zeros[pos] <= numbers.ones[pos];
+    let least_common_value = numbers.zeros[pos] > numbers.ones[pos];
+
+    for val in vec {
+        let bit = val.bit(pos);
+        if bit == most_common_value {
+            vec_most_common.push(val);
+        } else if bit == least_common_value {
+            vec_least_common.push(val);
+        }
+    }
+    (vec_most_common, vec_least_common)
+}
+
+/// Oxygen generator rating, the word left keeping the most common bits from the left
+///
+/// Panics if there are no words
+pub fn oxygen(vec: &[Word]) -> Word {
+    Criterion::OXYGEN.filter(vec).unwrap().survivor
+}
+
+/// CO2 scrubber rating, the word left keeping the least common bits from the left
+///
+/// Panics if there are no words
+pub fn co2(vec: &[Word]) -> Word {
+    Criterion::CO2.filter(vec).unwrap().survivor
+}
+
+/// Product of the values of two words, fails if it can't be counted with `C`
+fn product<C: Count>(a: &Word, b: &Word) -> Result<C> {
+    a.to_count::<C>()?
+        .try_mul(&b.to_count()?)
+        .ok_or_else(|| overflow("rating product"))
+}
+
+/// Gamma rate multiplied by the epsilon rate
+///
+/// Words wider than 32 bits can overflow `u64`, `power_consumption::<BigUint>` with the
+/// `bigint` feature never does.
+pub fn power_consumption<C: Count>(number_of_bits: usize, vec: Vec<Word>) -> Result<C> {
+    let (gamma, epsilon) = count_numbers_in_column(number_of_bits, vec).rates();
+    product(&gamma, &epsilon)
+}
+
+/// Oxygen generator rating multiplied by the CO2 scrubber rating, both found walking a
+/// `Trie` of the words
+///
+/// Like `power_consumption`, words wider than 32 bits can overflow `u64` but not `BigUint`.
+pub fn life_support_rating<C: Count>(vec: &[Word]) -> Result<C> {
+    let trie = Trie::from_words(vec, ScanOrder::MostSignificantFirst)
+        .ok_or_else(|| Error::NoSolution(String::from("empty diagnostic report")))?;
+    let oxygen_val = trie.filter(&Criterion::OXYGEN).unwrap().survivor;
+    let co2_val = trie.filter(&Criterion::CO2).unwrap().survivor;
+    product(&oxygen_val, &co2_val)
+}
+
+/// Solver of the day, part 1 is the power consumption and part 2 the life support rating
+///
+/// Both are counted with `u64`, as the answers are, so reports wider than 32 bits can fail
+/// with `Error::Overflow`; call `power_consumption` and `life_support_rating` with `BigUint`
+/// for those.
+pub struct Day03;
+
+impl Solver for Day03 {
+    const DAY: u32 = 3;
+
+    type Input = (usize, Vec<Word>);
+
+    fn parse<R: BufRead>(&self, reader: R) -> Result<(usize, Vec<Word>)> {
+        Ok(parse_strings(reader)?)
+    }
+
+    fn part1(&self, (number_of_bits, vec): &(usize, Vec<Word>)) -> Result<Answer> {
+        Ok(power_consumption::<u64>(*number_of_bits, vec.clone())?.into())
+    }
+
+    fn part2(&self, (_, vec): &(usize, Vec<Word>)) -> Result<Answer> {
+        Ok(life_support_rating::<u64>(vec)?.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    //use std::io;
+
+    use super::*;
+    use std::io::BufReader;
+
+    fn words(len: usize, vals: &[u64]) -> Vec<Word> {
+        vals.iter().map(|val| Word::from_u64(*val, len)).collect()
+    }
+
+    fn values(words: Vec<Word>) -> Vec<u64> {
+        words.iter().map(|word| word.to_u64().unwrap()).collect()
+    }
+
+    fn split((most, least): (Vec<Word>, Vec<Word>)) -> (Vec<u64>, Vec<u64>) {
+        (values(most), values(least))
+    }
+
+    const REPORT: [u64; 12] = [
+        0b00100, 0b11110, 0b10110, 0b10111, 0b10101, 0b01111, 0b00111, 0b11100, 0b10000, 0b11001,
+        0b00010, 0b01010,
+    ];
+
+    #[test]
+    fn test_binary_numbers_new() {
+        let numbers = BinaryNumbers::new();
+        assert_eq!(0, numbers.ones.len());
+        assert_eq!(0, numbers.zeros.len());
+        assert_eq!(0, numbers.word_len);
+    }
+
+    #[test]
+    fn test_parse_strings() {
+        let lstr = b"010101\n101010\n111111" as &[u8];
+        let vals = [0b010101, 0b101010, 0b111111];
+        let reader = BufReader::new(lstr);
+        let lstr_len = 6;
+
+        let (number_of_bits, vec) = parse_strings(reader).unwrap();
+        assert_eq!(lstr_len, number_of_bits);
+        assert_eq!(3, vec.len());
+        for (val, expected) in vec.iter().zip(vals.iter()) {
+            assert_eq!(Some(*expected), val.to_u64());
+        }
+    }
+
+    #[test]
+    fn test_parse_strings_error_with_different_sizes() {
+        let lstr = b"010101\n1010\n111\n000000000" as &[u8];
+        let reader = BufReader::new(lstr);
+
+        match parse_strings(reader) {
+            Err(ParseError::WordLength { loc, expected }) => {
+                assert_eq!(Location::new(2, 1, "1010"), loc);
+                assert_eq!(6, expected);
+            }
+            _ => panic!("expected a word length error"),
+        }
+    }
+
+    #[test]
+    fn test_parse_strings_error_not_binary() {
+        let lstr = b"010\n101\n131\n000" as &[u8];
+        let reader = BufReader::new(lstr);
+
+        match parse_strings(reader) {
+            Err(ParseError::InvalidDigit(loc)) => assert_eq!(Location::new(3, 2, "3"), loc),
+            _ => panic!("expected an invalid digit"),
+        }
+    }
+
+    #[test]
+    fn test_count_numbers() {
+        for i in 1..10 {
+            let input = vec![Word::zeros(i); 10];
+            let numbers = count_numbers_in_column(i, input.clone());
+            assert_eq!(i, numbers.word_len);
+            for (ones, zeros) in numbers.ones.iter().zip(numbers.zeros.iter()) {
+                assert_eq!(0, *ones);
+                assert_eq!(10, *zeros);
+            }
+        }
+
+        for i in 1usize..10 {
+            let input = vec![Word::zeros(i).complement(); 10];
+            let numbers = count_numbers_in_column(i, input.clone());
+            assert_eq!(i, numbers.word_len);
+            for (ones, zeros) in numbers.ones.iter().zip(numbers.zeros.iter()) {
+                assert_eq!(10, *ones);
+                assert_eq!(0, *zeros);
+            }
+        }
+    }
+
+    #[test]
+    fn test_rates() {
+        let mut numbers = BinaryNumbers::new();
+        numbers.word_len = 5;
+        numbers.ones = vec![0, 1, 1, 0, 1];
+        numbers.zeros = vec![1, 0, 0, 1, 0];
+        let (gamma, epsilon) = numbers.rates();
+        assert_eq!(Some(22), gamma.to_u64());
+        assert_eq!(Some(9), epsilon.to_u64());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_filter_by_count_panic_pos_outofbounds() {
+        let inputs = vec![Word::zeros(3); 10];
+        let (_, _) = filter_by_count(5, 3, inputs);
+    }
+
+    #[test]
+    fn test_filter_by_count() {
+        let inputs = vec![0b0000, 0b0001, 0b0010, 0b0100];
+
+        let (vec_most_common, vec_least_common) = split(filter_by_count(0, 4, words(4, &inputs)));
+        assert_eq!(3, vec_most_common.len());
+        assert_eq!(1, vec_least_common.len());
+
+        assert_eq!(1, vec_least_common[0]);
+        assert_eq!(0, vec_most_common[0]);
+        assert_eq!(2, vec_most_common[1]);
+        assert_eq!(4, vec_most_common[2]);
+
+        let inputs = vec![0b1111, 0b1110, 0b1101, 0b1011];
+
+        let (vec_most_common, vec_least_common) = split(filter_by_count(0, 4, words(4, &inputs)));
+        assert_eq!(3, vec_most_common.len());
+        assert_eq!(1, vec_least_common.len());
+
+        assert_eq!(0b1110, vec_least_common[0]);
+
+        assert_eq!(0b1111, vec_most_common[0]);
+        assert_eq!(0b1101, vec_most_common[1]);
+        assert_eq!(0b1011, vec_most_common[2]);
+
+        let inputs = vec![0b1111, 0b1110, 0b1101, 0b1011];
+
+        let (vec_most_common, vec_least_common) = split(filter_by_count(1, 4, words(4, &inputs)));
+        assert_eq!(3, vec_most_common.len());
+        assert_eq!(1, vec_least_common.len());
+
+        assert_eq!(0b1101, vec_least_common[0]);
+
+        assert_eq!(0b1111, vec_most_common[0]);
+        assert_eq!(0b1110, vec_most_common[1]);
+        assert_eq!(0b1011, vec_most_common[2]);
+
+        let inputs = vec![0b1111, 0b1110, 0b1101, 0b1011];
+
+        let (vec_most_common, vec_least_common) = split(filter_by_count(3, 4, words(4, &inputs)));
+        assert_eq!(4, vec_most_common.len());
+        assert_eq!(0, vec_least_common.len());
+
+        assert_eq!(0b1111, vec_most_common[0]);
+        assert_eq!(0b1110, vec_most_common[1]);
+        assert_eq!(0b1101, vec_most_common[2]);
+        assert_eq!(0b1011, vec_most_common[3]);
+    }
+
+    #[test]
+    fn test_filter_by_count_complex() {
+        let input = words(5, &REPORT);
+        let (input, _) = filter_by_count(4, 5, input);
+        assert_eq!(7, input.len());
+
+        let (input, _) = filter_by_count(3, 5, input);
+        assert_eq!(4, input.len());
+
+        let (input, _) = filter_by_count(2, 5, input);
+        assert_eq!(3, input.len());
+
+        let (input, _) = filter_by_count(1, 5, input);
+        assert_eq!(2, input.len());
+
+        let (input, _) = filter_by_count(0, 5, input);
+        assert_eq!(vec![23], values(input));
+
+        let mut input = words(5, &REPORT);
+        let remaining = [1, 2, 3, 4, 7];
+
+        for i in (0..=4).rev() {
+            let (a, _) = filter_by_count(i, 5, input.clone());
+            input = a;
+            assert_eq!(remaining[i], input.len());
+            if input.len() == 1 {
+                break;
+            }
+        }
+        assert_eq!(vec![23], values(input));
+    }
+
+    #[test]
+    fn test_oxygen_simple() {
+        let oxygen_val = oxygen(&words(5, &[0b00100, 0b11111, 0b01111]));
+        assert_eq!(Some(15), oxygen_val.to_u64());
+
+        let oxygen_val = oxygen(&words(5, &[0b00100, 0b11111, 0b00110]));
+        assert_eq!(Some(6), oxygen_val.to_u64());
+    }
+
+    #[test]
+    fn test_oxygen() {
+        let oxygen_val = oxygen(&words(5, &REPORT));
+        assert_eq!(Some(23), oxygen_val.to_u64());
+    }
+
+    #[test]
+    fn test_c02() {
+        let co2_val = co2(&words(5, &REPORT));
+        assert_eq!(Some(10), co2_val.to_u64());
+    }
+
+    #[test]
+    fn test_wide_report() {
+        // the example report with 123 leading bits, ones for the first word
+        let report: String = REPORT
+            .iter()
+            .enumerate()
+            .map(|(i, val)| {
+                let lead = if i == 0 { "1" } else { "0" };
+                format!("{}{:05b}\n", lead.repeat(123), val)
+            })
+            .collect();
+        let (number_of_bits, vec) = parse_strings(report.as_bytes()).unwrap();
+        assert_eq!(128, number_of_bits);
+
+        let (gamma, epsilon) = count_numbers_in_column(number_of_bits, vec.clone()).rates();
+        assert_eq!(Some(22), gamma.to_u64());
+        assert_eq!(format!("{}01001", "1".repeat(123)), epsilon.to_string());
+        assert!(power_consumption::<u64>(number_of_bits, vec.clone()).is_err());
+
+        // the first word is the least common from the first bit
+        assert_eq!(vec[0], co2(&vec));
+        assert_eq!(Some(23), oxygen(&vec).to_u64());
+        assert!(life_support_rating::<u64>(&vec).is_err());
+        assert!(life_support_rating::<u64>(&[]).is_err());
+
+        let input = (number_of_bits, vec);
+        assert!(matches!(Day03.part1(&input), Err(Error::Overflow(_))));
+        assert!(matches!(Day03.part2(&input), Err(Error::Overflow(_))));
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn test_wide_report_bigint() {
+        use aoc_common::BigUint;
+
+        let high_word = format!("1{}\n", "0".repeat(99));
+        let report = format!("{}{}0{}\n", high_word, high_word, "1".repeat(99));
+        let (number_of_bits, vec) = parse_strings(report.as_bytes()).unwrap();
+        let one = BigUint::from(1_u32);
+        let high = &one << 99;
+        let low = &high - &one;
+        assert_eq!(
+            &high * &low,
+            power_consumption::<BigUint>(number_of_bits, vec.clone()).unwrap()
+        );
+        assert_eq!(&high * &low, life_support_rating::<BigUint>(&vec).unwrap());
+    }
+}
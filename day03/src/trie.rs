@@ -0,0 +1,174 @@
+use crate::{Criterion, Filtered, Round, ScanOrder, Word};
+
+/// Node of the trie, the words below it and its children for a 0 and a 1
+#[derive(Debug, Clone, Copy, Default)]
+struct Node {
+    count: usize,
+    /// Indices of the children in the node list, 0 if missing as the root is no one's child
+    children: [usize; 2],
+}
+
+/// Binary trie of the words of a report, each node counting the words below it
+///
+/// Built once, it computes ratings walking a single path instead of rescanning the words
+/// left every round, the bits are stored in the scan order the trie is built for.
+#[derive(Debug, Clone)]
+pub struct Trie {
+    nodes: Vec<Node>,
+    width: usize,
+    order: ScanOrder,
+}
+
+impl Trie {
+    /// Empty trie of words of `width` bits scanned in `order`
+    pub fn new(width: usize, order: ScanOrder) -> Trie {
+        Trie {
+            nodes: vec![Node::default()],
+            width,
+            order,
+        }
+    }
+
+    /// Trie of `words` for ratings scanning the bits in `order`, None if there are no words
+    ///
+    /// # Panics
+    ///
+    /// If the words have different widths
+    pub fn from_words(words: &[Word], order: ScanOrder) -> Option<Trie> {
+        let mut trie = Trie::new(words.first()?.len(), order);
+        for word in words {
+            trie.insert(word);
+        }
+        Some(trie)
+    }
+
+    /// Bit position scanned at `depth`
+    fn pos(&self, depth: usize) -> usize {
+        match self.order {
+            ScanOrder::MostSignificantFirst => self.width - 1 - depth,
+            ScanOrder::LeastSignificantFirst => depth,
+        }
+    }
+
+    /// Adds a word
+    ///
+    /// # Panics
+    ///
+    /// If the word does not have the width of the trie
+    pub fn insert(&mut self, word: &Word) {
+        assert_eq!(self.width, word.len(), "word of a different width");
+        let mut node = 0;
+        self.nodes[node].count += 1;
+        for depth in 0..self.width {
+            let bit = word.bit(self.pos(depth)) as usize;
+            if self.nodes[node].children[bit] == 0 {
+                self.nodes.push(Node::default());
+                self.nodes[node].children[bit] = self.nodes.len() - 1;
+            }
+            node = self.nodes[node].children[bit];
+            self.nodes[node].count += 1;
+        }
+    }
+
+    /// Number of words, duplicates included
+    pub fn len(&self) -> usize {
+        self.nodes[0].count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn count(&self, child: usize) -> usize {
+        match child {
+            0 => 0,
+            child => self.nodes[child].count,
+        }
+    }
+
+    /// Word left by `criterion` and its rounds, as `Criterion::filter` on the words inserted;
+    /// None if there are no words
+    ///
+    /// # Panics
+    ///
+    /// If the criterion scans the bits in another order than the trie
+    pub fn filter(&self, criterion: &Criterion) -> Option<Filtered> {
+        assert_eq!(
+            self.order, criterion.order,
+            "trie built for another scan order"
+        );
+        if self.is_empty() {
+            return None;
+        }
+        let mut survivor = Word::zeros(self.width);
+        let mut trace = vec![];
+        let mut node = 0;
+        for depth in 0..self.width {
+            let [zeros, ones] = self.nodes[node].children.map(|child| self.count(child));
+            let bit = criterion.bit(ones, zeros);
+            // past a single word the path is followed without rounds
+            if ones + zeros > 1 {
+                trace.push(Round {
+                    pos: self.pos(depth),
+                    bit,
+                    remaining: if bit { ones } else { zeros },
+                });
+            }
+            survivor.set(self.pos(depth), bit);
+            node = self.nodes[node].children[bit as usize];
+        }
+        Some(Filtered { survivor, trace })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn words(len: usize, vals: &[u64]) -> Vec<Word> {
+        vals.iter().map(|val| Word::from_u64(*val, len)).collect()
+    }
+
+    const REPORT: [u64; 12] = [
+        0b00100, 0b11110, 0b10110, 0b10111, 0b10101, 0b01111, 0b00111, 0b11100, 0b10000, 0b11001,
+        0b00010, 0b01010,
+    ];
+
+    #[test]
+    fn test_insert() {
+        let trie = Trie::from_words(&words(5, &REPORT), ScanOrder::MostSignificantFirst).unwrap();
+        assert_eq!(12, trie.len());
+        let [zeros, ones] = trie.nodes[0].children.map(|child| trie.count(child));
+        assert_eq!((5, 7), (zeros, ones));
+        assert!(Trie::from_words(&[], ScanOrder::MostSignificantFirst).is_none());
+        assert!(Trie::new(5, ScanOrder::LeastSignificantFirst).is_empty());
+    }
+
+    #[test]
+    fn test_filter_matches_scan() {
+        let report = words(5, &REPORT);
+        let mut duplicates = words(3, &[0b101, 0b101, 0b111, 0b000, 0b010]);
+        duplicates.extend(words(3, &[0b101, 0b110]));
+        for criterion in [Criterion::OXYGEN, Criterion::CO2] {
+            for order in [
+                ScanOrder::MostSignificantFirst,
+                ScanOrder::LeastSignificantFirst,
+            ] {
+                let criterion = Criterion { order, ..criterion };
+                for words in [&report, &duplicates] {
+                    let trie = Trie::from_words(words, order).unwrap();
+                    assert_eq!(criterion.filter(words), trie.filter(&criterion));
+                }
+            }
+        }
+        let trie = Trie::new(5, ScanOrder::MostSignificantFirst);
+        assert_eq!(None, trie.filter(&Criterion::OXYGEN));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_filter_other_order() {
+        let trie = Trie::from_words(&words(5, &REPORT), ScanOrder::LeastSignificantFirst).unwrap();
+        trie.filter(&Criterion::OXYGEN);
+    }
+}
@@ -0,0 +1,184 @@
+use aoc_common::count::overflow;
+use aoc_common::{Count, Result};
+use std::fmt;
+
+const LIMB_BITS: usize = u64::BITS as usize;
+
+/// Binary word of any width, the bits are indexed from the least significant one
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Word {
+    /// Bits in groups of 64, the least significant group first, unused bits are 0
+    limbs: Vec<u64>,
+    len: usize,
+}
+
+impl Word {
+    /// Word of `len` bits all set to 0
+    pub fn zeros(len: usize) -> Word {
+        Word {
+            limbs: vec![0; len.div_ceil(LIMB_BITS)],
+            len,
+        }
+    }
+
+    /// Word of `len` bits holding the lowest `len` bits of `val`
+    pub fn from_u64(val: u64, len: usize) -> Word {
+        let mut word = Word::zeros(len);
+        for i in 0..len.min(LIMB_BITS) {
+            word.set(i, val & (1 << i) != 0);
+        }
+        word
+    }
+
+    /// Parses a string of '0' and '1', the most significant bit first
+    pub fn from_binary(digits: &str) -> Option<Word> {
+        let mut word = Word::zeros(digits.len());
+        for (i, c) in digits.bytes().rev().enumerate() {
+            match c {
+                b'0' => (),
+                b'1' => word.set(i, true),
+                _ => return None,
+            }
+        }
+        Some(word)
+    }
+
+    /// Number of bits
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Value of the bit at `pos`
+    ///
+    /// # Panics
+    ///
+    /// If `pos` is not smaller than the length
+    pub fn bit(&self, pos: usize) -> bool {
+        assert!(pos < self.len, "bit {} out of a {} bit word", pos, self.len);
+        self.limbs[pos / LIMB_BITS] & (1 << (pos % LIMB_BITS)) != 0
+    }
+
+    /// Sets the bit at `pos` to `val`
+    ///
+    /// # Panics
+    ///
+    /// If `pos` is not smaller than the length
+    pub fn set(&mut self, pos: usize, val: bool) {
+        assert!(pos < self.len, "bit {} out of a {} bit word", pos, self.len);
+        let mask = 1 << (pos % LIMB_BITS);
+        if val {
+            self.limbs[pos / LIMB_BITS] |= mask;
+        } else {
+            self.limbs[pos / LIMB_BITS] &= !mask;
+        }
+    }
+
+    /// Word with every bit flipped
+    pub fn complement(&self) -> Word {
+        let mut word = Word {
+            limbs: self.limbs.iter().map(|limb| !limb).collect(),
+            len: self.len,
+        };
+        if let Some(last) = word.limbs.last_mut() {
+            if !self.len.is_multiple_of(LIMB_BITS) {
+                *last &= (1 << (self.len % LIMB_BITS)) - 1;
+            }
+        }
+        word
+    }
+
+    /// Value of the word, None if it has more than 64 significant bits
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.limbs.split_first() {
+            None => Some(0),
+            Some((low, high)) if high.iter().all(|limb| *limb == 0) => Some(*low),
+            _ => None,
+        }
+    }
+
+    /// Value of the word counted with `C`, fails if it doesn't fit
+    pub fn to_count<C: Count>(&self) -> Result<C> {
+        // shifts by half a group as `C` may not hold 2^64
+        let half = C::from_u64(1 << (LIMB_BITS / 2));
+        let mut val = C::zero();
+        for limb in self.limbs.iter().rev() {
+            for part in [limb >> (LIMB_BITS / 2), limb & 0xffff_ffff] {
+                val = val
+                    .try_mul(&half)
+                    .and_then(|val| val.try_add(&C::from_u64(part)))
+                    .ok_or_else(|| overflow("diagnostic word"))?;
+            }
+        }
+        Ok(val)
+    }
+}
+
+impl fmt::Display for Word {
+    /// Binary digits, the most significant first
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digits: String = (0..self.len)
+            .rev()
+            .map(|i| if self.bit(i) { '1' } else { '0' })
+            .collect();
+        f.pad(&digits)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_from_binary() {
+        let word = Word::from_binary("10110").unwrap();
+        assert_eq!(5, word.len());
+        assert_eq!(Some(22), word.to_u64());
+        assert_eq!(Word::from_u64(22, 5), word);
+        assert_eq!("10110", word.to_string());
+        assert!(Word::from_binary("102").is_none());
+        assert_eq!(Some(0), Word::from_binary("").unwrap().to_u64());
+    }
+
+    #[test]
+    fn test_wide_word() {
+        let digits = format!("1{}1", "0".repeat(126));
+        let mut word = Word::from_binary(&digits).unwrap();
+        assert_eq!(128, word.len());
+        assert!(word.bit(127) && word.bit(0) && !word.bit(64));
+        assert_eq!(None, word.to_u64());
+        assert_eq!(digits, word.to_string());
+
+        word.set(127, false);
+        assert_eq!(Some(1), word.to_u64());
+    }
+
+    #[test]
+    fn test_complement() {
+        assert_eq!(Some(9), Word::from_u64(22, 5).complement().to_u64());
+
+        let word = Word::zeros(1000).complement();
+        assert!((0..1000).all(|i| word.bit(i)));
+        assert_eq!(Word::zeros(1000), word.complement());
+        assert_eq!(Some(u64::MAX), Word::zeros(64).complement().to_u64());
+    }
+
+    #[test]
+    fn test_to_count() {
+        assert_eq!(22, Word::from_u64(22, 5).to_count::<u64>().unwrap());
+        let max = Word::zeros(64).complement();
+        assert_eq!(u64::MAX, max.to_count::<u64>().unwrap());
+        assert!(Word::zeros(65).complement().to_count::<u64>().is_err());
+        // leading zeros don't overflow
+        assert_eq!(1, Word::from_u64(1, 200).to_count::<u64>().unwrap());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bit_out_of_bounds() {
+        Word::zeros(3).bit(3);
+    }
+}
@@ -0,0 +1,216 @@
+use crate::Game;
+use std::collections::VecDeque;
+
+/// Line of a win pattern on a board
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Line {
+    /// Index of the pattern among those played
+    pub pattern: usize,
+    /// Index of the line among those of the pattern, as given by `WinPattern::lines`
+    pub index: usize,
+    /// Cells of the line, counted row by row
+    pub cells: Vec<usize>,
+}
+
+/// Change of a bingo game
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event {
+    /// A number is drawn, `draw` counts the numbers drawn so far, this one included
+    Drawn { draw: usize, number: usize },
+    /// A cell of a board holds the number drawn
+    Marked { board: usize, cell: usize },
+    /// A board wins completing `line`, the score is the sum of its unmarked numbers times
+    /// the number drawn, wide enough to never overflow
+    Won {
+        board: usize,
+        draw: usize,
+        score: u128,
+        line: Line,
+    },
+}
+
+impl Event {
+    /// Standing of the board winning with this event, None if it is not a win
+    pub fn into_standing(self) -> Option<Standing> {
+        match self {
+            Event::Won {
+                board,
+                draw,
+                score,
+                line,
+            } => Some(Standing {
+                board,
+                draw,
+                score,
+                line,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Iterator over the events of a game as the numbers are drawn
+///
+/// Created with `Game::events`.
+pub struct Events<'a> {
+    game: Game,
+    numbers: std::slice::Iter<'a, usize>,
+    pending: VecDeque<Event>,
+}
+
+impl Iterator for Events<'_> {
+    type Item = Event;
+
+    fn next(&mut self) -> Option<Event> {
+        while self.pending.is_empty() {
+            let number = self.numbers.next()?;
+            let pending = &mut self.pending;
+            self.game.step(*number, |event| pending.push_back(event));
+        }
+        self.pending.pop_front()
+    }
+}
+
+/// Board that won a game
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Standing {
+    pub board: usize,
+    /// Number of numbers drawn when the board won
+    pub draw: usize,
+    pub score: u128,
+    pub line: Line,
+}
+
+/// Boards of a game in the order they won, and those that never did
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Ranking {
+    /// Winners by the draw they won at, then by index
+    pub winners: Vec<Standing>,
+    /// Indexes of the boards that never won, in order
+    pub losers: Vec<usize>,
+}
+
+impl Ranking {
+    /// The `k`-th board to win, counting from 0
+    pub fn kth(&self, k: usize) -> Option<&Standing> {
+        self.winners.get(k)
+    }
+
+    /// Last board to win
+    pub fn last(&self) -> Option<&Standing> {
+        self.winners.last()
+    }
+}
+
+impl Game {
+    /// Events of the game drawing `numbers`
+    pub fn events(self, numbers: &[usize]) -> Events<'_> {
+        Events {
+            game: self,
+            numbers: numbers.iter(),
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Plays the game drawing `numbers` until every board wins or they run out
+    pub fn ranking(mut self, numbers: &[usize]) -> Ranking {
+        let mut winners = vec![];
+        for number in numbers {
+            if self.is_over() {
+                break;
+            }
+            self.step(*number, |event| winners.extend(event.into_standing()));
+        }
+        let losers = (0..self.boards())
+            .filter(|board| self.won_at(*board).is_none())
+            .collect();
+        Ranking { winners, losers }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{file_reader, make_boards, WinPattern};
+
+    const SMALL: &[u8] = b"5,1,9,3,7,2\n\n1 2 3\n4 5 6\n7 8 9\n\n4 8 7\n6 1 9\n3 2 5\n";
+
+    /// The boards of `SMALL` and a board of zeros
+    fn small_game() -> (Vec<usize>, Vec<u32>, usize) {
+        let (draws, mut numbers, side) = file_reader(SMALL).unwrap();
+        numbers.extend([0; 9]);
+        (draws, numbers, side)
+    }
+
+    #[test]
+    fn test_events() {
+        let (draws, numbers, side) = small_game();
+        let boards = make_boards(&numbers, side);
+        let events: Vec<Event> = Game::new(&boards).events(&draws[..2]).collect();
+        assert_eq!(
+            vec![
+                Event::Drawn { draw: 1, number: 5 },
+                Event::Marked { board: 0, cell: 4 },
+                Event::Marked { board: 1, cell: 8 },
+                Event::Drawn { draw: 2, number: 1 },
+                Event::Marked { board: 0, cell: 0 },
+                Event::Marked { board: 1, cell: 4 },
+            ],
+            events
+        );
+
+        let won: Vec<Event> = Game::new(&boards)
+            .events(&draws)
+            .filter(|event| matches!(event, Event::Won { .. }))
+            .collect();
+        assert_eq!(
+            vec![
+                Event::Won {
+                    board: 1,
+                    draw: 5,
+                    score: (4 + 8 + 6 + 2) * 7,
+                    line: Line {
+                        pattern: 1,
+                        index: 2,
+                        cells: vec![2, 5, 8]
+                    }
+                },
+                Event::Won {
+                    board: 0,
+                    draw: 6,
+                    score: (4 + 5 + 6 + 7 + 8 + 9 - 5 - 7 - 9) * 2,
+                    line: Line {
+                        pattern: 0,
+                        index: 0,
+                        cells: vec![0, 1, 2]
+                    }
+                },
+            ],
+            won
+        );
+    }
+
+    #[test]
+    fn test_ranking() {
+        let (draws, numbers, side) = small_game();
+        let boards = make_boards(&numbers, side);
+        let ranking = Game::new(&boards).ranking(&draws);
+        let order: Vec<(usize, usize)> =
+            ranking.winners.iter().map(|s| (s.board, s.draw)).collect();
+        assert_eq!(vec![(1, 5), (0, 6)], order);
+        assert_eq!(vec![2], ranking.losers);
+        assert_eq!(Some(0), ranking.kth(1).map(|s| s.board));
+        assert_eq!(None, ranking.kth(2));
+        assert_eq!(ranking.kth(1), ranking.last());
+
+        // the diagonals of the first two boards, the last one wins drawing 0
+        let mut more_draws = draws.clone();
+        more_draws.push(0);
+        let ranking = Game::with_patterns(&[WinPattern::Diagonals], &boards).ranking(&more_draws);
+        let order: Vec<(usize, usize)> =
+            ranking.winners.iter().map(|s| (s.board, s.draw)).collect();
+        assert_eq!(vec![(0, 3), (1, 5), (2, 7)], order);
+        assert_eq!(0, ranking.last().unwrap().score);
+        assert!(ranking.losers.is_empty());
+    }
+}
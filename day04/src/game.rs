@@ -0,0 +1,244 @@
+use crate::{BingoBoard, Event, Line, WinPattern};
+use std::collections::HashMap;
+
+/// Lines of the patterns played on boards of a given side
+#[derive(Debug)]
+struct Layout {
+    lines: Vec<Line>,
+    /// Indexes of the lines every cell belongs to
+    cell_lines: Vec<Vec<usize>>,
+}
+
+impl Layout {
+    fn new(patterns: &[WinPattern], side: usize) -> Layout {
+        let mut lines = vec![];
+        for (pattern, pattern_lines) in patterns.iter().map(|p| p.lines(side)).enumerate() {
+            for (index, cells) in pattern_lines.into_iter().enumerate() {
+                lines.push(Line {
+                    pattern,
+                    index,
+                    cells,
+                });
+            }
+        }
+        let mut cell_lines = vec![vec![]; side * side];
+        for (i, line) in lines.iter().enumerate() {
+            for cell in &line.cells {
+                cell_lines[*cell].push(i);
+            }
+        }
+        Layout { lines, cell_lines }
+    }
+}
+
+/// Marks and line counters of a board
+#[derive(Debug)]
+struct BoardState {
+    side: usize,
+    marked: Vec<bool>,
+    /// Marked cells of every line of the layout
+    hits: Vec<usize>,
+    unmarked_sum: u64,
+    won_at: Option<usize>,
+}
+
+/// Bingo game played one draw at a time
+///
+/// Every number is indexed with the cells holding it and every board counts the marked
+/// cells of each line, so a draw only visits the cells with the number drawn.
+#[derive(Debug)]
+pub struct Game {
+    layouts: HashMap<usize, Layout>,
+    /// Board and cell of every occurrence of each number
+    index: HashMap<usize, Vec<(usize, usize)>>,
+    states: Vec<BoardState>,
+    draws: usize,
+    /// Number of boards that have won
+    winners: usize,
+}
+
+impl Game {
+    /// Game of `boards` won completing a row or a column
+    pub fn new(boards: &[BingoBoard]) -> Game {
+        Game::with_patterns(&WinPattern::STANDARD, boards)
+    }
+
+    /// Game of `boards` won completing a line of any of `patterns`
+    pub fn with_patterns(patterns: &[WinPattern], boards: &[BingoBoard]) -> Game {
+        let mut layouts = HashMap::new();
+        let mut index: HashMap<usize, Vec<(usize, usize)>> = HashMap::new();
+        let mut states = Vec::with_capacity(boards.len());
+        for (i, board) in boards.iter().enumerate() {
+            let layout = layouts
+                .entry(board.side())
+                .or_insert_with(|| Layout::new(patterns, board.side()));
+            for (cell, number) in board.numbers().iter().enumerate() {
+                index.entry(*number as usize).or_default().push((i, cell));
+            }
+            states.push(BoardState {
+                side: board.side(),
+                marked: vec![false; board.numbers().len()],
+                hits: vec![0; layout.lines.len()],
+                unmarked_sum: board.numbers().iter().map(|n| u64::from(*n)).sum(),
+                won_at: None,
+            });
+        }
+        Game {
+            layouts,
+            index,
+            states,
+            draws: 0,
+            winners: 0,
+        }
+    }
+
+    /// Marks the cells holding `number`, returns the indexes of the boards winning with it
+    pub fn draw(&mut self, number: usize) -> Vec<usize> {
+        let mut winners = vec![];
+        self.step(number, |event| {
+            if let Event::Won { board, .. } = event {
+                winners.push(board);
+            }
+        });
+        winners
+    }
+
+    /// Draws `number` passing every change of the game to `on_event`, in order
+    pub(crate) fn step<F: FnMut(Event)>(&mut self, number: usize, mut on_event: F) {
+        self.draws += 1;
+        on_event(Event::Drawn {
+            draw: self.draws,
+            number,
+        });
+        for &(board, cell) in self.index.get(&number).into_iter().flatten() {
+            let state = &mut self.states[board];
+            if state.marked[cell] {
+                continue;
+            }
+            state.marked[cell] = true;
+            state.unmarked_sum -= number as u64;
+            on_event(Event::Marked { board, cell });
+            let layout = &self.layouts[&state.side];
+            for &line in &layout.cell_lines[cell] {
+                state.hits[line] += 1;
+                if state.hits[line] == layout.lines[line].cells.len() && state.won_at.is_none() {
+                    state.won_at = Some(self.draws);
+                    self.winners += 1;
+                    on_event(Event::Won {
+                        board,
+                        draw: self.draws,
+                        score: u128::from(state.unmarked_sum) * number as u128,
+                        line: layout.lines[line].clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    /// Number of boards
+    pub fn boards(&self) -> usize {
+        self.states.len()
+    }
+
+    /// Number of numbers drawn
+    pub fn draws(&self) -> usize {
+        self.draws
+    }
+
+    /// Number of numbers drawn when `board` won, None if it has not won yet
+    pub fn won_at(&self, board: usize) -> Option<usize> {
+        self.states[board].won_at
+    }
+
+    /// Whether the cell `cell` of `board`, counted row by row, has been drawn
+    pub fn is_marked(&self, board: usize, cell: usize) -> bool {
+        self.states[board].marked[cell]
+    }
+
+    /// Sum of the numbers of `board` not drawn yet
+    pub fn unmarked_sum(&self, board: usize) -> u64 {
+        self.states[board].unmarked_sum
+    }
+
+    /// Whether every board has won
+    pub fn is_over(&self) -> bool {
+        self.winners == self.states.len()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{make_boards, Generator};
+
+    #[test]
+    fn test_draw() {
+        let numbers = (0..25).collect::<Vec<u32>>();
+        let boards = make_boards(&numbers, 5);
+        let mut game = Game::new(&boards);
+        assert_eq!(300, game.unmarked_sum(0));
+        for n in [0, 6, 12, 18] {
+            assert!(game.draw(n).is_empty());
+        }
+        // numbers not on the board and drawn twice change nothing
+        assert!(game.draw(99).is_empty());
+        assert!(game.draw(6).is_empty());
+        assert_eq!(300 - 36, game.unmarked_sum(0));
+        assert!(game.is_marked(0, 6) && !game.is_marked(0, 7));
+        assert_eq!(None, game.won_at(0));
+
+        let mut diagonals = Game::with_patterns(&[WinPattern::Diagonals], &boards);
+        for n in [0, 6, 12, 18] {
+            diagonals.draw(n);
+        }
+        assert_eq!(vec![0], diagonals.draw(24));
+        assert_eq!(Some(5), diagonals.won_at(0));
+        assert!(diagonals.is_over());
+        // a board wins once
+        assert!(diagonals.draw(4).is_empty());
+    }
+
+    #[test]
+    fn test_matches_scan() {
+        // 60 boards sharing numbers and 80 draws, some not on any board and some repeated
+        let mut generator = Generator::new(1);
+        let numbers = generator.boards(60, 5, 50);
+        let mut draws = generator.draws(60);
+        draws.extend_from_within(..20);
+        let boards = make_boards(&numbers, 5);
+        let patterns = [
+            vec![WinPattern::Rows, WinPattern::Columns],
+            vec![WinPattern::Diagonals, WinPattern::Corners],
+        ];
+        for patterns in patterns {
+            let won_at: Vec<Option<usize>> = boards
+                .iter()
+                .map(|board| (1..=draws.len()).find(|k| board.wins(&patterns, &draws[..*k])))
+                .collect();
+            let mut game = Game::with_patterns(&patterns, &boards);
+            for j in 1..=draws.len() {
+                game.draw(draws[j - 1]);
+                for (i, board) in boards.iter().enumerate() {
+                    let expected = won_at[i].filter(|k| *k <= j);
+                    assert_eq!(expected, game.won_at(i), "board {} after {}", i, j);
+                    let sum = board.sum_of_unmarked(&draws[..j]);
+                    assert_eq!(sum, game.unmarked_sum(i));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_many_boards() {
+        let numbers = Generator::new(2).boards(5000, 5, 1000);
+        let boards = make_boards(&numbers, 5);
+        let mut game = Game::new(&boards);
+        let mut winners = 0;
+        for n in 0..1000 {
+            winners += game.draw(n).len();
+        }
+        assert_eq!(5000, winners);
+        assert!(game.is_over());
+        assert!((0..5000).all(|i| game.unmarked_sum(i) == 0));
+    }
+}
@@ -0,0 +1,137 @@
+//! Random bingo games, for the tests and for `day04 --generate SEED`
+
+use aoc_common::Rng;
+use std::fmt::Write;
+
+/// Seeded source of random bingo games, the same seed always gives the same games
+///
+/// Boards never repeat a number, so the games it makes are accepted by `file_reader` and
+/// `check_boards`.
+#[derive(Debug, Clone)]
+pub struct Generator {
+    rng: Rng,
+}
+
+impl Generator {
+    /// Generator of the games of `seed`
+    pub fn new(seed: u64) -> Generator {
+        Generator {
+            rng: Rng::new(seed),
+        }
+    }
+
+    /// `count` different numbers of `0..max` in random order
+    fn sample(&mut self, count: usize, max: u32) -> Vec<u32> {
+        let mut pool: Vec<u32> = (0..max).collect();
+        for i in 0..count {
+            let j = i + self.rng.below((pool.len() - i) as u64) as usize;
+            pool.swap(i, j);
+        }
+        pool.truncate(count);
+        pool
+    }
+
+    /// Numbers of `boards` boards of `side` x `side` numbers of `0..max`, row by row
+    ///
+    /// # Panics
+    ///
+    /// If `max` is smaller than the number of cells of a board
+    pub fn boards(&mut self, boards: usize, side: usize, max: u32) -> Vec<u32> {
+        assert!(
+            side * side <= max as usize,
+            "a {0}x{0} board needs {1} different numbers",
+            side,
+            side * side
+        );
+        (0..boards)
+            .flat_map(|_| self.sample(side * side, max))
+            .collect()
+    }
+
+    /// Every number of `0..max` once, in random order
+    pub fn draws(&mut self, max: u32) -> Vec<usize> {
+        self.sample(max as usize, max)
+            .into_iter()
+            .map(|n| n as usize)
+            .collect()
+    }
+}
+
+/// Text of a game as read by `file_reader`: the draws and the boards separated by blank lines
+///
+/// # Panics
+///
+/// If `side` is 0
+pub fn to_input(draws: &[usize], numbers: &[u32], side: usize) -> String {
+    let width = numbers.iter().max().map_or(1, |max| max.to_string().len());
+    let draws: Vec<String> = draws.iter().map(|n| n.to_string()).collect();
+    let mut input = draws.join(",");
+    input.push('\n');
+    for (i, row) in numbers.chunks(side).enumerate() {
+        if i % side == 0 {
+            input.push('\n');
+        }
+        let row: Vec<String> = row.iter().map(|n| format!("{:>1$}", n, width)).collect();
+        writeln!(input, "{}", row.join(" ")).unwrap();
+    }
+    input
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{check_boards, file_reader, make_boards, Game};
+
+    #[test]
+    fn test_same_seed() {
+        let mut a = Generator::new(7);
+        let mut b = Generator::new(7);
+        assert_eq!(a.boards(3, 5, 100), b.boards(3, 5, 100));
+        assert_eq!(a.draws(100), b.draws(100));
+        assert_ne!(
+            Generator::new(8).boards(3, 5, 100),
+            Generator::new(7).boards(3, 5, 100)
+        );
+    }
+
+    #[test]
+    fn test_valid_games() {
+        for seed in 0..20 {
+            let mut generator = Generator::new(seed);
+            let numbers = generator.boards(10, 4, 16 + seed as u32);
+            assert_eq!(160, numbers.len());
+            assert_eq!(Ok(()), check_boards(&numbers, 4));
+
+            let mut draws = generator.draws(16 + seed as u32);
+            draws.sort_unstable();
+            assert_eq!((0..16 + seed as usize).collect::<Vec<_>>(), draws);
+        }
+
+        // drawing every number every board wins
+        let mut generator = Generator::new(1);
+        let numbers = generator.boards(500, 5, 90);
+        let draws = generator.draws(90);
+        let ranking = Game::new(&make_boards(&numbers, 5)).ranking(&draws);
+        assert_eq!(500, ranking.winners.len());
+    }
+
+    #[test]
+    fn test_to_input() {
+        assert_eq!(
+            "3,1\n\n 0  1\n12  3\n\n 4  5\n 6  7\n",
+            to_input(&[3, 1], &[0, 1, 12, 3, 4, 5, 6, 7], 2)
+        );
+
+        let mut generator = Generator::new(3);
+        let numbers = generator.boards(4, 3, 50);
+        let draws = generator.draws(50);
+        let input = to_input(&draws, &numbers, 3);
+        assert_eq!((draws, numbers, 3), file_reader(input.as_bytes()).unwrap());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_boards_too_few_numbers() {
+        Generator::new(0).boards(1, 5, 24);
+    }
+}
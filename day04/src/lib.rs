@@ -0,0 +1,811 @@
+//! Day 4: Giant Squid, plays bingo to find the first and the last winning boards
+
+pub mod events;
+pub mod game;
+pub mod generate;
+pub mod pattern;
+pub mod search;
+
+pub use events::{Event, Events, Line, Ranking, Standing};
+pub use game::Game;
+pub use generate::{to_input, Generator};
+pub use pattern::WinPattern;
+pub use search::{Goal, Outcome, Search};
+
+use aoc_common::{count::overflow, Answer, Error, Location, Result, Solver};
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::fmt;
+use std::io;
+
+aoc_common::parse_error! {
+    /// Errors found parsing the drawn numbers and the boards
+    pub enum ParseError {
+        /// A drawn number that is not a valid number
+        InvalidDraw(Location),
+        /// A board cell that is not a valid number
+        InvalidCell(Location),
+        /// A board that can't be played, located at the offending number, row or first row
+        InvalidBoard {
+            loc: Location,
+            why: BoardError,
+        },
+    }
+
+    fn fmt(&self, f) {
+        ParseError::InvalidDraw(loc) => {
+            write!(f, "{}: invalid drawn number '{}'", loc, loc.token)
+        }
+        ParseError::InvalidCell(loc) => {
+            write!(f, "{}: invalid board number '{}'", loc, loc.token)
+        }
+        ParseError::InvalidBoard { loc, why } => write!(f, "{}: {}", loc, why),
+    }
+}
+
+type ParseResult<T> = std::result::Result<T, ParseError>;
+
+/// Problem of a board making it unplayable, boards are counted from 0
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BoardError {
+    /// A board with fewer numbers than its side squared
+    Incomplete {
+        board: usize,
+        len: usize,
+        expected: usize,
+    },
+    /// A row with more or fewer numbers than the side of the boards
+    RowLength {
+        board: usize,
+        len: usize,
+        expected: usize,
+    },
+    /// A number found twice in the same board
+    Duplicate { board: usize, number: u32 },
+}
+
+impl fmt::Display for BoardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BoardError::Incomplete {
+                board,
+                len,
+                expected,
+            } => write!(
+                f,
+                "board {} has {} numbers, expected {}",
+                board, len, expected
+            ),
+            BoardError::RowLength {
+                board,
+                len,
+                expected,
+            } => write!(
+                f,
+                "row of board {} has {} numbers, expected {}",
+                board, len, expected
+            ),
+            BoardError::Duplicate { board, number } => {
+                write!(f, "board {} has number {} twice", board, number)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BoardError {}
+
+/// A square bingo board, the numbers are stored row by row and can be borrowed from the input
+pub struct BingoBoard<'a> {
+    board: Cow<'a, [u32]>,
+    side: usize,
+}
+
+impl<'a> fmt::Display for BingoBoard<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut ostr = String::new();
+
+        for (i, val) in self.board.iter().enumerate() {
+            ostr.push_str(format!("{:2} ", val).as_str());
+            if (i % self.side) == (self.side - 1) {
+                ostr.push('\n');
+            }
+        }
+        write!(f, "{}", ostr)
+    }
+}
+
+impl<'a> BingoBoard<'a> {
+    /// Side of the boards of the standard game
+    pub const BOARD_SIDE: usize = 5;
+
+    /// 5x5 board with all the numbers set to 0
+    pub fn new() -> BingoBoard<'a> {
+        BingoBoard::with_side(BingoBoard::BOARD_SIDE)
+    }
+
+    /// Board of `side` x `side` numbers all set to 0
+    pub fn with_side(side: usize) -> BingoBoard<'a> {
+        BingoBoard {
+            board: Cow::Owned(vec![0_u32; side * side]),
+            side,
+        }
+    }
+
+    /// Board borrowing its numbers, given row by row, its side is the square root of their
+    /// count
+    ///
+    /// Panics if the number of numbers is not a square
+    pub fn from_numbers(vals: &'a [u32]) -> BingoBoard<'a> {
+        let side = vals.len().isqrt();
+        assert_eq!(
+            side * side,
+            vals.len(),
+            "a bingo board needs a square number of numbers"
+        );
+        BingoBoard {
+            board: Cow::Borrowed(vals),
+            side,
+        }
+    }
+
+    /// Number of rows and of columns
+    pub fn side(&self) -> usize {
+        self.side
+    }
+
+    /// Numbers of the board, row by row
+    pub fn numbers(&self) -> &[u32] {
+        &self.board
+    }
+
+    /// Copies the numbers, row by row, into the board
+    pub fn initialize_board_numbers(&mut self, vals: &[u32]) {
+        for (i, val) in vals.iter().enumerate() {
+            self.board.to_mut()[i] = *val;
+        }
+    }
+
+    /// Indexes of the lines of `pattern` completed by the called numbers
+    pub fn check(&self, pattern: &WinPattern, numbers: &[usize]) -> Vec<usize> {
+        pattern
+            .lines(self.side)
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| {
+                line.iter()
+                    .all(|cell| numbers.contains(&(self.board[*cell] as usize)))
+            })
+            .map(|(i, _)| i)
+            .collect()
+    }
+
+    /// Whether the called numbers complete a line of any of the patterns
+    pub fn wins(&self, patterns: &[WinPattern], numbers: &[usize]) -> bool {
+        patterns
+            .iter()
+            .any(|pattern| !self.check(pattern, numbers).is_empty())
+    }
+
+    /// Indexes of the rows completed by the called numbers
+    pub fn check_rows(&self, numbers: &[usize]) -> Vec<usize> {
+        self.check(&WinPattern::Rows, numbers)
+    }
+
+    /// Indexes of the columns completed by the called numbers
+    pub fn check_cols(&self, numbers: &[usize]) -> Vec<usize> {
+        self.check(&WinPattern::Columns, numbers)
+    }
+
+    /// Sum of the numbers of the board that have not been called
+    pub fn sum_of_unmarked(&self, numbers: &[usize]) -> u64 {
+        let mut sum = 0;
+        for val in self.board.iter().map(|x| *x as usize) {
+            if numbers.contains(&val) {
+                continue;
+            }
+            sum += val as u64;
+        }
+        sum
+    }
+}
+
+impl<'a> Default for BingoBoard<'a> {
+    fn default() -> Self {
+        BingoBoard::new()
+    }
+}
+
+/// Reads the drawn numbers from the first line and the numbers of all the boards after it,
+/// returns them with the side of the boards, the number of numbers in the first board row
+/// or `BOARD_SIDE` if there are no boards
+///
+/// Blank lines are skipped, a board ends once it has as many rows as columns. Rows of
+/// another length, an incomplete last board and numbers repeated in a board are reported
+/// with the index of the board.
+pub fn file_reader<I: io::BufRead>(reader: I) -> ParseResult<(Vec<usize>, Vec<u32>, usize)> {
+    let mut input_numbers = vec![];
+    let mut board_numbers = vec![];
+    let mut side = 0;
+    // index, first row and numbers of the board being read
+    let mut board = 0;
+    let mut board_loc = None;
+    let mut seen = HashSet::new();
+
+    for (i, line) in reader.lines().enumerate() {
+        let tmp_str = line?;
+        if i == 0 {
+            for x in tmp_str.split(',') {
+                match x.trim().parse::<usize>() {
+                    Ok(val) => input_numbers.push(val),
+                    Err(_) => {
+                        return Err(ParseError::InvalidDraw(Location::in_line(1, &tmp_str, x)))
+                    }
+                }
+            }
+        } else if !tmp_str.trim().is_empty() {
+            if side > 0 && seen.len() == side * side {
+                end_board(&mut board, &mut board_loc, &mut seen, side)?;
+            }
+            let row_loc = Location::new(i + 1, 1, &tmp_str);
+            board_loc.get_or_insert_with(|| row_loc.clone());
+            let row_start = board_numbers.len();
+            for x in tmp_str.split_whitespace() {
+                let loc = Location::in_line(i + 1, &tmp_str, x);
+                match x.parse::<u32>() {
+                    Ok(val) if !seen.insert(val) => {
+                        let why = BoardError::Duplicate { board, number: val };
+                        return Err(ParseError::InvalidBoard { loc, why });
+                    }
+                    Ok(val) => board_numbers.push(val),
+                    Err(_) => return Err(ParseError::InvalidCell(loc)),
+                }
+            }
+            let len = board_numbers.len() - row_start;
+            if side == 0 {
+                side = len;
+            } else if len != side {
+                let why = BoardError::RowLength {
+                    board,
+                    len,
+                    expected: side,
+                };
+                return Err(ParseError::InvalidBoard { loc: row_loc, why });
+            }
+        }
+    }
+    end_board(&mut board, &mut board_loc, &mut seen, side)?;
+
+    if side == 0 {
+        side = BingoBoard::BOARD_SIDE;
+    }
+    Ok((input_numbers, board_numbers, side))
+}
+
+/// Checks that the board being read, if any, is complete and starts the next one
+fn end_board(
+    board: &mut usize,
+    board_loc: &mut Option<Location>,
+    seen: &mut HashSet<u32>,
+    side: usize,
+) -> ParseResult<()> {
+    if let Some(loc) = board_loc.take() {
+        if seen.len() != side * side {
+            let why = BoardError::Incomplete {
+                board: *board,
+                len: seen.len(),
+                expected: side * side,
+            };
+            return Err(ParseError::InvalidBoard { loc, why });
+        }
+        *board += 1;
+        seen.clear();
+    }
+    Ok(())
+}
+
+/// Checks that `numbers` split in boards of `side` x `side` numbers, as `make_boards` does,
+/// gives only complete boards without repeated numbers
+pub fn check_boards(numbers: &[u32], side: usize) -> std::result::Result<(), BoardError> {
+    let mut seen = HashSet::new();
+    for (board, window) in numbers.chunks(side * side).enumerate() {
+        if window.len() != side * side {
+            return Err(BoardError::Incomplete {
+                board,
+                len: window.len(),
+                expected: side * side,
+            });
+        }
+        seen.clear();
+        if let Some(number) = window.iter().find(|number| !seen.insert(**number)) {
+            return Err(BoardError::Duplicate {
+                board,
+                number: *number,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Splits the numbers of the boards into boards of `side` x `side` numbers, the last one is
+/// padded with zeros if it is incomplete; `check_boards` rejects such numbers
+///
+/// Panics if `side` is 0
+pub fn make_boards(numbers: &[u32], side: usize) -> Vec<BingoBoard<'_>> {
+    let mut boards = Vec::<BingoBoard>::new();
+    for window in numbers.chunks(side * side) {
+        let mut b = BingoBoard::with_side(side);
+        b.initialize_board_numbers(window);
+        boards.push(b);
+    }
+    boards
+}
+
+/// First board to win, the lowest index on ties; None if no board wins
+pub fn find_winner_board(input_numbers: &[usize], boards: &Vec<BingoBoard>) -> Option<Standing> {
+    find_winner_board_with(&WinPattern::STANDARD, input_numbers, boards)
+}
+
+/// `find_winner_board` completing lines of any of `patterns`
+pub fn find_winner_board_with(
+    patterns: &[WinPattern],
+    input_numbers: &[usize],
+    boards: &[BingoBoard],
+) -> Option<Standing> {
+    Game::with_patterns(patterns, boards)
+        .events(input_numbers)
+        .find_map(Event::into_standing)
+}
+
+/// Last board to win, the lowest index on ties; boards that never win are ignored, None if
+/// no board wins
+pub fn find_last_winner_board(
+    input_numbers: &[usize],
+    boards: &Vec<BingoBoard>,
+) -> Option<Standing> {
+    find_last_winner_board_with(&WinPattern::STANDARD, input_numbers, boards)
+}
+
+/// `find_last_winner_board` completing lines of any of `patterns`
+pub fn find_last_winner_board_with(
+    patterns: &[WinPattern],
+    input_numbers: &[usize],
+    boards: &[BingoBoard],
+) -> Option<Standing> {
+    let winners = Game::with_patterns(patterns, boards)
+        .ranking(input_numbers)
+        .winners;
+    let last_draw = winners.last()?.draw;
+    winners
+        .into_iter()
+        .find(|standing| standing.draw == last_draw)
+}
+
+/// Score of a winner board as an answer: sum of the unmarked numbers times the last number
+/// called
+pub fn score(winner: Option<Standing>) -> Result<Answer> {
+    let winner = winner.ok_or_else(|| Error::NoSolution(String::from("no board wins")))?;
+    u64::try_from(winner.score)
+        .map(Answer::from)
+        .map_err(|_| overflow("score of the winner board"))
+}
+
+/// Solver of the day, part 1 scores the first winning board and part 2 the last one
+pub struct Day04;
+
+impl Solver for Day04 {
+    const DAY: u32 = 4;
+
+    type Input = (Vec<usize>, Vec<u32>, usize);
+
+    fn parse<R: io::BufRead>(&self, reader: R) -> Result<(Vec<usize>, Vec<u32>, usize)> {
+        Ok(file_reader(reader)?)
+    }
+
+    fn part1(&self, (input_numbers, board_numbers, side): &Self::Input) -> Result<Answer> {
+        let boards = make_boards(board_numbers, *side);
+        score(find_winner_board(input_numbers, &boards))
+    }
+
+    fn part2(&self, (input_numbers, board_numbers, side): &Self::Input) -> Result<Answer> {
+        let boards = make_boards(board_numbers, *side);
+        score(find_last_winner_board(input_numbers, &boards))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const F: &[u8] = b"7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1\n
+\n
+22 13 17 11  0\n
+ 8  2 23  4 24\n
+21  9 14 16  7\n
+ 6 10  3 18  5\n
+ 1 12 20 15 19\n
+\n
+ 3 15  0  2 22\n
+ 9 18 13 17  5\n
+19  8  7 25 23\n
+20 11 10 24  4\n
+14 21 16 12  6\n
+\n
+14 21 17 24  4\n
+10 16 15  9 19\n
+18  8 23 26 20\n
+22 11 13  6  5\n
+2  0 12  3  7" as &[u8];
+
+    #[test]
+    fn integration_test_solution_1() {
+        let reader = io::BufReader::new(F);
+
+        let (input_numbers, board_numbers, side) = file_reader(reader).unwrap();
+        let boards = make_boards(&board_numbers, side);
+
+        let winner = find_winner_board(input_numbers.as_slice(), &boards).unwrap();
+        let sum = boards[winner.board].sum_of_unmarked(&input_numbers[0..winner.draw]);
+
+        assert_eq!(2, winner.board);
+        assert_eq!(188, sum);
+        assert_eq!(12, winner.draw);
+        assert_eq!(24, input_numbers[winner.draw - 1]);
+        assert_eq!(188 * 24, winner.score);
+    }
+
+    #[test]
+    fn test_find_last_winner_board() {
+        let reader = io::BufReader::new(F);
+
+        let (input_numbers, board_numbers, side) = file_reader(reader).unwrap();
+        let boards = make_boards(&board_numbers, side);
+
+        let last_winner = find_last_winner_board(input_numbers.as_slice(), &boards).unwrap();
+        let sum = boards[last_winner.board].sum_of_unmarked(&input_numbers[0..last_winner.draw]);
+
+        assert_eq!(1, last_winner.board);
+        assert_eq!(148, sum);
+        assert_eq!(15, last_winner.draw);
+        assert_eq!(13, input_numbers[last_winner.draw - 1]);
+        assert_eq!(148 * 13, last_winner.score);
+    }
+
+    #[test]
+    fn test_find_winner_board() {
+        let mut board = BingoBoard::new();
+        board.initialize_board_numbers(&[
+            14, 21, 17, 24, 4, 10, 16, 15, 9, 19, 18, 8, 23, 26, 20, 22, 11, 13, 6, 5, 2, 0, 12, 3,
+            7,
+        ]);
+
+        let boards = vec![board];
+        let input_numbers = &[7usize, 4, 9, 5, 11, 17, 23, 2, 0, 14, 21, 24];
+
+        let winner = find_winner_board(input_numbers, &boards).unwrap();
+        assert_eq!(0, winner.board);
+        assert_eq!(input_numbers.len(), winner.draw);
+        assert_eq!(24, input_numbers[winner.draw - 1]);
+    }
+
+    #[test]
+    fn test_bingoboard_initializeboardnumbers() {
+        let mut board = BingoBoard::new();
+        board.initialize_board_numbers(&(0..25).collect::<Vec<u32>>());
+        for i in 0usize..25 {
+            assert_eq!(i as u32, board.board[i]);
+        }
+    }
+
+    #[test]
+    fn test_file_reader() {
+        let reader = io::BufReader::new(F);
+        let (number_sequence, boards, side) = file_reader(reader).unwrap();
+        assert_eq!(5, side);
+        assert_eq!(27, number_sequence.len());
+        assert_eq!(7, number_sequence[0]);
+        assert_eq!(4, number_sequence[1]);
+        assert_eq!(1, number_sequence[number_sequence.len() - 1]);
+
+        assert_eq!(75, boards.len());
+
+        assert_eq!(22, boards[0]);
+        assert_eq!(19, boards[24]);
+
+        assert_eq!(3, boards[25]);
+        assert_eq!(6, boards[49]);
+
+        assert_eq!(14, boards[50]);
+        assert_eq!(7, boards[74]);
+    }
+
+    #[test]
+    fn test_file_reader_errors() {
+        let reader = io::BufReader::new(b"7,4,x9,5\n\n22 13" as &[u8]);
+        match file_reader(reader) {
+            Err(ParseError::InvalidDraw(loc)) => assert_eq!(Location::new(1, 5, "x9"), loc),
+            _ => panic!("expected an invalid draw"),
+        }
+
+        let reader = io::BufReader::new(b"7,4,9,5\n\n22 13\n 8 -2" as &[u8]);
+        match file_reader(reader) {
+            Err(ParseError::InvalidCell(loc)) => assert_eq!(Location::new(4, 4, "-2"), loc),
+            _ => panic!("expected an invalid cell"),
+        }
+    }
+
+    #[test]
+    fn test_makeboards() {
+        let v: Vec<u32> = (0u32..75).collect();
+
+        let boards = make_boards(&v, 5);
+
+        assert_eq!(3, boards.len());
+
+        assert_eq!(0, boards[0].board[0]);
+        assert_eq!(24, boards[0].board[24]);
+
+        assert_eq!(50, boards[2].board[0]);
+        assert_eq!(74, boards[2].board[24]);
+    }
+
+    #[test]
+    fn test_bingoboard_check_rows() {
+        let v: Vec<u32> = (0u32..24).collect();
+        let boards = make_boards(&v, 5);
+
+        let rows = boards[0].check_rows(&[0, 1, 2]);
+        assert_eq!(0, rows.len());
+
+        let rows = boards[0].check_rows(&[0, 1, 2, 3, 5, 6, 7, 9, 10, 11, 14]);
+        assert_eq!(0, rows.len());
+
+        let rows = boards[0].check_rows(&[0, 1, 2, 3, 4, 5, 6, 7, 9, 10, 11, 14]);
+        assert_eq!(1, rows.len());
+        assert!(rows.contains(&0usize));
+
+        let rows = boards[0].check_rows(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 14]);
+        assert_eq!(2, rows.len());
+        assert!(rows.contains(&0usize));
+        assert!(rows.contains(&1usize));
+
+        let rows =
+            boards[0].check_rows(&[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 14, 24, 23, 22, 21, 20]);
+        assert_eq!(3, rows.len());
+        assert!(rows.contains(&0usize));
+        assert!(rows.contains(&1usize));
+        assert!(rows.contains(&4usize));
+    }
+
+    #[test]
+    fn test_bingoboard_check_cols() {
+        let v: Vec<u32> = (0u32..24).collect();
+        let boards = make_boards(&v, 5);
+
+        let cols = boards[0].check_cols(&[0, 1, 2]);
+        assert_eq!(0, cols.len());
+
+        let cols = boards[0].check_cols(&[0, 1, 5, 2, 10, 15, 20]);
+        assert_eq!(1, cols.len());
+        assert!(cols.contains(&0));
+
+        let cols = boards[0].check_cols(&[0, 1, 6, 2, 11, 16, 21, 22]);
+        assert_eq!(1, cols.len());
+        assert!(cols.contains(&1));
+
+        let cols = boards[0].check_cols(&[0, 1, 7, 2, 12, 17, 21, 22, 23]);
+        assert_eq!(1, cols.len());
+        assert!(cols.contains(&2));
+
+        let cols = boards[0].check_cols(&[0, 3, 8, 2, 13, 18, 21, 22, 23]);
+        assert_eq!(1, cols.len());
+        assert!(cols.contains(&3));
+
+        let cols = boards[0].check_cols(&[0, 4, 9, 2, 14, 19, 21, 22, 24]);
+        assert_eq!(1, cols.len());
+        assert!(cols.contains(&4));
+
+        let cols = boards[0].check_cols(&(0..24).collect::<Vec<usize>>());
+        assert_eq!(5, cols.len());
+        assert!(cols.contains(&0));
+    }
+
+    #[test]
+    fn test_sum_of_unmarked() {
+        let v: Vec<u32> = (0u32..25).collect();
+        let boards = make_boards(&v, 5);
+
+        assert_eq!(25 * 12, boards[0].sum_of_unmarked(&[]));
+        assert_eq!(25 * 12 - 23, boards[0].sum_of_unmarked(&[23]));
+
+        assert_eq!(25 * 12, boards[0].sum_of_unmarked(&[]));
+        assert_eq!(25 * 12 - 24 - 23, boards[0].sum_of_unmarked(&[23, 24]));
+    }
+
+    #[test]
+    fn test_from_numbers() {
+        let numbers = (0..25).collect::<Vec<u32>>();
+        let board = BingoBoard::from_numbers(&numbers);
+        assert_eq!(vec![1], board.check_rows(&[5, 6, 7, 8, 9]));
+        assert_eq!(300 - 35, board.sum_of_unmarked(&[5, 6, 7, 8, 9]));
+
+        let numbers = (0..9).collect::<Vec<u32>>();
+        let board = BingoBoard::from_numbers(&numbers);
+        assert_eq!(3, board.side());
+        assert_eq!(vec![2], board.check_cols(&[2, 5, 8]));
+    }
+
+    const SMALL: &[u8] = b"5,1,9,3,7,2\n\n1 2 3\n4 5 6\n7 8 9\n\n4 8 7\n6 1 9\n3 2 5\n";
+
+    #[test]
+    fn test_small_boards() {
+        let (input_numbers, board_numbers, side) = file_reader(SMALL).unwrap();
+        assert_eq!(3, side);
+        let boards = make_boards(&board_numbers, side);
+        assert_eq!(2, boards.len());
+        assert_eq!(3, boards[1].side());
+
+        let draw_and_board = |standing: Option<Standing>| standing.map(|s| (s.draw, s.board));
+        // the last column of the second board, then the first row of the first one
+        assert_eq!(
+            Some((5, 1)),
+            draw_and_board(find_winner_board(&input_numbers, &boards))
+        );
+        assert_eq!(
+            Some((6, 0)),
+            draw_and_board(find_last_winner_board(&input_numbers, &boards))
+        );
+
+        // 1 5 9 is a diagonal of the first board, 7 1 3 of the second one
+        let diagonals = [WinPattern::Diagonals];
+        assert_eq!(
+            Some((3, 0)),
+            draw_and_board(find_winner_board_with(&diagonals, &input_numbers, &boards))
+        );
+        assert_eq!(
+            Some((5, 1)),
+            draw_and_board(find_last_winner_board_with(
+                &diagonals,
+                &input_numbers,
+                &boards
+            ))
+        );
+        assert_eq!(vec![0], boards[0].check(&WinPattern::Diagonals, &[5, 1, 9]));
+        // 1 3 7 9 are the corners of the first board, the second one lacks a 4
+        let corners = [WinPattern::Corners, WinPattern::FullHouse];
+        assert_eq!(
+            Some((5, 0)),
+            draw_and_board(find_winner_board_with(&corners, &input_numbers, &boards))
+        );
+    }
+
+    #[test]
+    fn test_no_winner() {
+        let numbers = (0..25).collect::<Vec<u32>>();
+        let boards = make_boards(&numbers, 5);
+        assert_eq!(None, find_winner_board(&[0, 1, 2, 3], &boards));
+        assert_eq!(None, find_last_winner_board(&[0, 1, 2, 3], &boards));
+        assert!(score(None).is_err());
+        // five draws are enough
+        let winner = find_winner_board(&[0, 1, 2, 3, 4], &boards).unwrap();
+        assert_eq!((5, 0), (winner.draw, winner.board));
+    }
+
+    #[test]
+    fn test_large_numbers() {
+        let input = Day04
+            .parse(&b"70000,70001\n\n70000 70001\n70002 70003\n"[..])
+            .unwrap();
+        assert_eq!(
+            Answer::UInt((70002 + 70003) * 70001),
+            Day04.part1(&input).unwrap()
+        );
+
+        // the score does not fit in 64 bits
+        let max = u32::MAX as usize;
+        let input = Day04
+            .parse(
+                format!(
+                    "{},{}\n\n{} {}\n{} {}\n",
+                    max,
+                    max - 1,
+                    max,
+                    max - 1,
+                    max - 2,
+                    max - 3
+                )
+                .as_bytes(),
+            )
+            .unwrap();
+        let winner = find_winner_board(&input.0, &make_boards(&input.1, input.2)).unwrap();
+        assert_eq!((2 * max as u128 - 5) * (max as u128 - 1), winner.score);
+        assert!(matches!(Day04.part1(&input), Err(Error::Overflow(_))));
+        assert!(matches!(Day04.part2(&input), Err(Error::Overflow(_))));
+    }
+
+    #[test]
+    fn test_file_reader_invalid_boards() {
+        let cases: [(&[u8], Location, BoardError); 4] = [
+            (
+                b"1,2\n\n1 2\n3 4\n\n5 6\n7\n",
+                Location::new(7, 1, "7"),
+                BoardError::RowLength {
+                    board: 1,
+                    len: 1,
+                    expected: 2,
+                },
+            ),
+            (
+                b"1,2\n\n1 2\n3 4\n\n5 6\n\n7 8\n\n9 10\n",
+                Location::new(10, 1, "9 10"),
+                BoardError::Incomplete {
+                    board: 2,
+                    len: 2,
+                    expected: 4,
+                },
+            ),
+            (
+                b"1,2\n1 2\n3 4\n5 6\n",
+                Location::new(4, 1, "5 6"),
+                BoardError::Incomplete {
+                    board: 1,
+                    len: 2,
+                    expected: 4,
+                },
+            ),
+            (
+                b"1,2\n\n1 2\n3 4\n\n5 6\n7 5\n",
+                Location::new(7, 3, "5"),
+                BoardError::Duplicate {
+                    board: 1,
+                    number: 5,
+                },
+            ),
+        ];
+        for (input, expected_loc, expected_why) in cases {
+            match file_reader(input) {
+                Err(ParseError::InvalidBoard { loc, why }) => {
+                    assert_eq!((expected_loc, expected_why), (loc, why))
+                }
+                _ => panic!("expected an invalid board"),
+            }
+        }
+
+        // boards without blank lines between them
+        let (_, numbers, side) = file_reader(b"1,2\n1 2\n3 4\n5 6\n7 8\n" as &[u8]).unwrap();
+        assert_eq!((8, 2), (numbers.len(), side));
+
+        let why = BoardError::Duplicate {
+            board: 3,
+            number: 7,
+        };
+        let error = ParseError::InvalidBoard {
+            loc: Location::new(12, 4, "7"),
+            why,
+        };
+        assert_eq!("12:4: board 3 has number 7 twice", error.to_string());
+    }
+
+    #[test]
+    fn test_check_boards() {
+        let numbers = (0..50).collect::<Vec<u32>>();
+        assert_eq!(Ok(()), check_boards(&numbers, 5));
+        assert_eq!(
+            Err(BoardError::Incomplete {
+                board: 1,
+                len: 24,
+                expected: 25
+            }),
+            check_boards(&numbers[..49], 5)
+        );
+
+        let mut numbers = numbers;
+        numbers[40] = 30;
+        assert_eq!(
+            Err(BoardError::Duplicate {
+                board: 1,
+                number: 30
+            }),
+            check_boards(&numbers, 5)
+        );
+    }
+}
@@ -0,0 +1,92 @@
+/// Cells of a board that win when they are all marked
+///
+/// Each pattern is a set of lines, a board wins as soon as any line of the patterns played
+/// is complete. Cells are indexed row by row.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WinPattern {
+    Rows,
+    Columns,
+    /// Both diagonals
+    Diagonals,
+    /// The four corners together
+    Corners,
+    /// Every cell of the board
+    FullHouse,
+    /// Lines given as (row, column) pairs, empty ones or with cells out of the board never
+    /// win
+    Custom(Vec<Vec<(usize, usize)>>),
+}
+
+impl WinPattern {
+    /// Patterns of the standard game, any row or column
+    pub const STANDARD: [WinPattern; 2] = [WinPattern::Rows, WinPattern::Columns];
+
+    /// Cells of each line of the pattern for a board of `side` x `side` cells
+    pub fn lines(&self, side: usize) -> Vec<Vec<usize>> {
+        match self {
+            WinPattern::Rows => (0..side)
+                .map(|row| (0..side).map(|col| row * side + col).collect())
+                .collect(),
+            WinPattern::Columns => (0..side)
+                .map(|col| (0..side).map(|row| row * side + col).collect())
+                .collect(),
+            WinPattern::Diagonals => vec![
+                (0..side).map(|i| i * side + i).collect(),
+                (0..side).map(|i| i * side + side - 1 - i).collect(),
+            ],
+            WinPattern::Corners if side == 0 => vec![],
+            WinPattern::Corners => {
+                let mut corners = vec![0, side - 1, side * (side - 1), side * side - 1];
+                corners.sort_unstable();
+                corners.dedup();
+                vec![corners]
+            }
+            WinPattern::FullHouse => vec![(0..side * side).collect()],
+            WinPattern::Custom(masks) => masks
+                .iter()
+                .filter(|mask| !mask.is_empty())
+                .filter(|mask| mask.iter().all(|(row, col)| *row < side && *col < side))
+                .map(|mask| mask.iter().map(|(row, col)| row * side + col).collect())
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_lines() {
+        assert_eq!(
+            vec![vec![0, 1, 2], vec![3, 4, 5], vec![6, 7, 8]],
+            WinPattern::Rows.lines(3)
+        );
+        assert_eq!(
+            vec![vec![0, 3, 6], vec![1, 4, 7], vec![2, 5, 8]],
+            WinPattern::Columns.lines(3)
+        );
+        assert_eq!(
+            vec![vec![0, 4, 8], vec![2, 4, 6]],
+            WinPattern::Diagonals.lines(3)
+        );
+        assert_eq!(vec![vec![0, 2, 6, 8]], WinPattern::Corners.lines(3));
+        assert_eq!(vec![vec![0]], WinPattern::Corners.lines(1));
+        assert_eq!(
+            vec![(0..9).collect::<Vec<_>>()],
+            WinPattern::FullHouse.lines(3)
+        );
+    }
+
+    #[test]
+    fn test_custom_lines() {
+        // a plus sign, an empty mask and a mask out of a 3x3 board
+        let pattern = WinPattern::Custom(vec![
+            vec![(0, 1), (1, 0), (1, 1), (1, 2), (2, 1)],
+            vec![],
+            vec![(0, 0), (3, 3)],
+        ]);
+        assert_eq!(vec![vec![1, 3, 4, 5, 7]], pattern.lines(3));
+        assert_eq!(2, pattern.lines(4).len());
+    }
+}
@@ -0,0 +1,540 @@
+//! Draw orders crafted to make a chosen board win first or last
+//!
+//! A board wins first with the numbers of one of its lines as long as they don't complete a
+//! line of another board, so the shortest such line is the shortest draw order. To win last
+//! the numbers drawn before the winning one must complete a line of every other board but
+//! none of the chosen one; finding the fewest such numbers is a set cover problem, solved
+//! by a branch and bound search over the line completed on every board.
+
+use crate::{BingoBoard, WinPattern};
+use std::collections::HashMap;
+
+/// Most nodes `Search::plan` explores by default before giving up on proving optimality
+pub const MAX_NODES: usize = 1_000_000;
+
+/// When the chosen board has to win
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Goal {
+    /// Before any other board, alone at its draw
+    First,
+    /// After every other board has won, alone at its draw
+    Last,
+}
+
+/// Result of a search
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    /// Numbers to draw, the board wins with the last one; `optimal` if no shorter order
+    /// exists, otherwise the search ran out of nodes before proving it
+    Found { draws: Vec<usize>, optimal: bool },
+    /// No draw order makes the board win as asked
+    Impossible,
+    /// The search ran out of nodes before finding an order or proving there is none
+    GaveUp,
+}
+
+/// Set of numbers, as bits indexed by the position of the number among those of the boards
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Set(Vec<u64>);
+
+impl Set {
+    fn new(len: usize) -> Set {
+        Set(vec![0; len.div_ceil(64)])
+    }
+
+    fn contains(&self, id: usize) -> bool {
+        self.0[id / 64] & (1 << (id % 64)) != 0
+    }
+
+    fn insert(&mut self, id: usize) {
+        self.0[id / 64] |= 1 << (id % 64);
+    }
+
+    fn remove(&mut self, id: usize) {
+        self.0[id / 64] &= !(1 << (id % 64));
+    }
+
+    fn len(&self) -> usize {
+        self.0.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    /// Numbers of `self` missing from `other`
+    fn missing(&self, other: &Set) -> usize {
+        self.0
+            .iter()
+            .zip(&other.0)
+            .map(|(a, b)| (a & !b).count_ones() as usize)
+            .sum()
+    }
+
+    /// Numbers of `self` not in `other`
+    fn difference(&self, other: &Set) -> Set {
+        Set(self.0.iter().zip(&other.0).map(|(a, b)| a & !b).collect())
+    }
+
+    fn is_subset(&self, other: &Set) -> bool {
+        self.0.iter().zip(&other.0).all(|(a, b)| a & !b == 0)
+    }
+
+    fn ids(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.0.len() * 64).filter(move |id| self.contains(*id))
+    }
+
+    /// Adds the numbers of `other`, returns those that were not in `self`
+    fn extend(&mut self, other: &Set) -> Vec<usize> {
+        let added: Vec<usize> = other.ids().filter(|id| !self.contains(*id)).collect();
+        for id in &added {
+            self.insert(*id);
+        }
+        added
+    }
+}
+
+/// Searches draw orders making a board win first or last
+///
+/// ```
+/// use day04::{make_boards, Game, Goal, Outcome, Search};
+///
+/// let numbers: Vec<u32> = (1..=8).chain([1, 2, 5, 6]).collect();
+/// let boards = make_boards(&numbers, 2);
+/// if let Outcome::Found { draws, optimal } = Search::new(&boards).plan(1, Goal::Last) {
+///     assert!(optimal);
+///     let ranking = Game::new(&boards).ranking(&draws);
+///     assert_eq!(1, ranking.last().unwrap().board);
+///     assert_eq!(draws.len(), ranking.last().unwrap().draw);
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Search {
+    /// Different numbers of the boards, a number is identified by its position here
+    numbers: Vec<usize>,
+    /// Numbers of each line of every board
+    lines: Vec<Vec<Set>>,
+    /// Most nodes explored by a search
+    pub max_nodes: usize,
+}
+
+impl Search {
+    /// Search on `boards` won completing a row or a column
+    pub fn new(boards: &[BingoBoard]) -> Search {
+        Search::with_patterns(&WinPattern::STANDARD, boards)
+    }
+
+    /// Search on `boards` won completing a line of any of `patterns`
+    pub fn with_patterns(patterns: &[WinPattern], boards: &[BingoBoard]) -> Search {
+        let mut numbers = vec![];
+        let mut ids = HashMap::new();
+        for board in boards {
+            for number in board.numbers() {
+                ids.entry(*number as usize).or_insert_with(|| {
+                    numbers.push(*number as usize);
+                    numbers.len() - 1
+                });
+            }
+        }
+        let lines = boards
+            .iter()
+            .map(|board| {
+                patterns
+                    .iter()
+                    .flat_map(|pattern| pattern.lines(board.side()))
+                    .map(|cells| {
+                        let mut line = Set::new(numbers.len());
+                        for cell in cells {
+                            line.insert(ids[&(board.numbers()[cell] as usize)]);
+                        }
+                        line
+                    })
+                    .collect()
+            })
+            .collect();
+        Search {
+            numbers,
+            lines,
+            max_nodes: MAX_NODES,
+        }
+    }
+
+    /// Shortest draw order making `board` win as `goal` asks
+    ///
+    /// # Panics
+    ///
+    /// If `board` is not the index of one of the boards
+    pub fn plan(&self, board: usize, goal: Goal) -> Outcome {
+        assert!(board < self.lines.len(), "there is no board {}", board);
+        match goal {
+            Goal::First => self.first(board),
+            Goal::Last => self.last(board),
+        }
+    }
+
+    fn draws<'a>(&self, ids: impl Iterator<Item = usize> + 'a) -> Vec<usize> {
+        ids.map(|id| self.numbers[id]).collect()
+    }
+
+    /// Whether drawing `drawn` completes a line of a board other than `board`
+    fn others_win(&self, board: usize, drawn: &Set) -> bool {
+        self.lines
+            .iter()
+            .enumerate()
+            .any(|(i, lines)| i != board && lines.iter().any(|line| line.is_subset(drawn)))
+    }
+
+    fn first(&self, board: usize) -> Outcome {
+        let line = self.lines[board]
+            .iter()
+            .filter(|line| !self.others_win(board, line))
+            .min_by_key(|line| line.len());
+        match line {
+            Some(line) => Outcome::Found {
+                draws: self.draws(line.ids()),
+                optimal: true,
+            },
+            None => Outcome::Impossible,
+        }
+    }
+
+    fn last(&self, board: usize) -> Outcome {
+        let mut state = State {
+            nodes: 0,
+            max_nodes: self.max_nodes,
+            best: None,
+            gave_up: false,
+        };
+        // the board wins completing `line` with `winning`, drawn after the others have won
+        for line in &self.lines[board] {
+            for winning in line.ids() {
+                let mut drawn = line.clone();
+                drawn.remove(winning);
+                let cover = Cover {
+                    winning,
+                    // lines with the winning number can't be completed before it is drawn
+                    targets: self.lines[board]
+                        .iter()
+                        .filter(|line| !line.contains(winning))
+                        .collect(),
+                    others: self
+                        .lines
+                        .iter()
+                        .enumerate()
+                        .filter(|(i, _)| *i != board)
+                        .map(|(_, lines)| lines.iter().filter(|l| !l.contains(winning)).collect())
+                        .collect(),
+                };
+                if cover.targets.iter().any(|target| target.is_subset(&drawn))
+                    || cover.others.iter().any(Vec::is_empty)
+                {
+                    continue;
+                }
+                let size = drawn.len();
+                cover.search(&mut drawn, size, &mut state);
+                if state.gave_up {
+                    break;
+                }
+            }
+        }
+        match (state.best, state.gave_up) {
+            (Some(draws), gave_up) => Outcome::Found {
+                draws: self.draws(draws.into_iter()),
+                optimal: !gave_up,
+            },
+            (None, false) => Outcome::Impossible,
+            (None, true) => Outcome::GaveUp,
+        }
+    }
+}
+
+/// Progress of a search for the board winning last
+struct State {
+    nodes: usize,
+    max_nodes: usize,
+    /// Ids of the shortest draw order found so far
+    best: Option<Vec<usize>>,
+    gave_up: bool,
+}
+
+/// Lines to complete before drawing the winning number of the chosen board
+struct Cover<'a> {
+    winning: usize,
+    /// Lines of the chosen board that must stay incomplete
+    targets: Vec<&'a Set>,
+    /// Lines of every other board that can be completed before the winning number
+    others: Vec<Vec<&'a Set>>,
+}
+
+impl Cover<'_> {
+    /// Completes a line of every other board adding to `drawn`, of `size` numbers
+    fn search(&self, drawn: &mut Set, size: usize, state: &mut State) {
+        if state.nodes == state.max_nodes {
+            state.gave_up = true;
+            return;
+        }
+        state.nodes += 1;
+
+        // branches on the board needing the most numbers to win, the most it needs is a
+        // lower bound of the numbers left to draw
+        let mut branch = None;
+        let mut bound = 0;
+        for lines in &self.others {
+            let needed = lines.iter().map(|line| line.missing(drawn)).min().unwrap();
+            if needed > bound {
+                bound = needed;
+                branch = Some(lines);
+            }
+        }
+        if let Some(best) = &state.best {
+            if size + bound + 1 >= best.len() {
+                return;
+            }
+        }
+        let lines = match branch {
+            Some(lines) => lines,
+            None => {
+                let mut draws: Vec<usize> = drawn.ids().collect();
+                draws.push(self.winning);
+                state.best = Some(draws);
+                return;
+            }
+        };
+
+        // a line needing every number another one needs can't give shorter draws
+        let needs: Vec<Set> = lines.iter().map(|line| line.difference(drawn)).collect();
+        let mut lines: Vec<&Set> = (0..lines.len())
+            .filter(|i| {
+                !needs.iter().enumerate().any(|(j, other)| {
+                    other.is_subset(&needs[*i]) && (j < *i || *other != needs[*i])
+                })
+            })
+            .map(|i| &needs[i])
+            .collect();
+        lines.sort_by_key(|line| line.len());
+        for line in lines {
+            let added = drawn.extend(line);
+            if !self.targets.iter().any(|target| target.is_subset(drawn)) {
+                self.search(drawn, size + added.len(), state);
+            }
+            for id in added {
+                drawn.remove(id);
+            }
+            if state.gave_up {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{file_reader, make_boards, Game, Generator};
+
+    const SMALL: &[u8] = b"0
+1 2 3
+4 5 6
+7 8 9
+
+1 4 7
+10 11 12
+13 14 15
+
+3 6 9
+2 5 8
+16 17 18
+";
+
+    /// Checks that drawing `draws` makes `board` win as `goal` asks, at the last draw
+    fn check(
+        boards: &[BingoBoard],
+        patterns: &[WinPattern],
+        board: usize,
+        goal: Goal,
+        draws: &[usize],
+    ) {
+        let ranking = Game::with_patterns(patterns, boards).ranking(draws);
+        let standing = match goal {
+            Goal::First => ranking.kth(0),
+            Goal::Last => ranking.last(),
+        }
+        .unwrap();
+        assert_eq!((board, draws.len()), (standing.board, standing.draw));
+        let alone = ranking.winners.iter().filter(|s| s.draw == standing.draw);
+        assert_eq!(1, alone.count(), "{:?}", draws);
+        if goal == Goal::Last {
+            assert!(ranking.losers.is_empty());
+        }
+    }
+
+    /// Length of the shortest draw order making `board` win as `goal` asks, trying every
+    /// order of every subset of the numbers
+    fn brute_force(boards: &[BingoBoard], board: usize, goal: Goal) -> Option<usize> {
+        let mut numbers: Vec<usize> = boards
+            .iter()
+            .flat_map(|b| b.numbers().iter().map(|n| *n as usize))
+            .collect();
+        numbers.sort_unstable();
+        numbers.dedup();
+        (1..1 << numbers.len())
+            .filter_map(|mask: u32| {
+                let drawn: Vec<usize> = (0..numbers.len())
+                    .filter(|i| mask & (1 << i) != 0)
+                    .map(|i| numbers[i])
+                    .collect();
+                // the board wins with the last draw, the order of the others doesn't matter
+                (0..drawn.len())
+                    .map(|last| {
+                        let mut draws = drawn.clone();
+                        let number = draws.remove(last);
+                        draws.push(number);
+                        draws
+                    })
+                    .find(|draws| {
+                        let ranking = Game::new(boards).ranking(draws);
+                        let standing = match goal {
+                            Goal::First => ranking.kth(0),
+                            Goal::Last => ranking.last(),
+                        };
+                        matches!(standing, Some(s) if s.board == board && s.draw == draws.len())
+                            && ranking
+                                .winners
+                                .iter()
+                                .filter(|s| s.draw == draws.len())
+                                .count()
+                                == 1
+                            && (goal == Goal::First || ranking.losers.is_empty())
+                    })
+            })
+            .map(|draws| draws.len())
+            .min()
+    }
+
+    #[test]
+    fn test_first() {
+        let (_, numbers, side) = file_reader(SMALL).unwrap();
+        let boards = make_boards(&numbers, side);
+        let search = Search::new(&boards);
+        for board in 0..boards.len() {
+            match search.plan(board, Goal::First) {
+                Outcome::Found { draws, optimal } => {
+                    assert!(optimal);
+                    assert_eq!(3, draws.len());
+                    check(&boards, &WinPattern::STANDARD, board, Goal::First, &draws);
+                }
+                outcome => panic!("board {} can win first, got {:?}", board, outcome),
+            }
+        }
+    }
+
+    #[test]
+    fn test_last() {
+        let (_, numbers, side) = file_reader(SMALL).unwrap();
+        let boards = make_boards(&numbers, side);
+        let search = Search::new(&boards);
+        let mut lengths = vec![];
+        for board in 0..boards.len() {
+            match search.plan(board, Goal::Last) {
+                Outcome::Found { draws, optimal } => {
+                    assert!(optimal);
+                    check(&boards, &WinPattern::STANDARD, board, Goal::Last, &draws);
+                    lengths.push(draws.len());
+                }
+                outcome => panic!("board {} can win last, got {:?}", board, outcome),
+            }
+        }
+        // 3, 6, 9 and 1, 4, 7 complete lines of two boards at once, the first board can't
+        // let the others win with its own lines but 2 and 3 also start its first row
+        assert_eq!(vec![7, 6, 6], lengths);
+        assert_eq!(
+            Outcome::Found {
+                draws: vec![2, 3, 10, 11, 12, 16, 1],
+                optimal: true
+            },
+            search.plan(0, Goal::Last)
+        );
+    }
+
+    #[test]
+    fn test_impossible() {
+        // boards with the same lines always win together
+        let boards = make_boards(&[1, 2, 3, 4, 4, 3, 2, 1], 2);
+        let search = Search::new(&boards);
+        for board in 0..2 {
+            for goal in [Goal::First, Goal::Last] {
+                assert_eq!(Outcome::Impossible, search.plan(board, goal));
+            }
+        }
+
+        // a single board wins both first and last
+        let boards = make_boards(&[1, 2, 3, 4], 2);
+        for goal in [Goal::First, Goal::Last] {
+            assert!(matches!(
+                Search::new(&boards).plan(0, goal),
+                Outcome::Found { .. }
+            ));
+        }
+    }
+
+    #[test]
+    fn test_patterns() {
+        // swapping two rows keeps the rows and columns of a board but not its diagonals
+        let numbers: Vec<u32> = (1..=9).chain([4, 5, 6, 1, 2, 3, 7, 8, 9]).collect();
+        let boards = make_boards(&numbers, 3);
+        let patterns = [WinPattern::Rows, WinPattern::Columns, WinPattern::Diagonals];
+        let search = Search::with_patterns(&patterns, &boards);
+        for goal in [Goal::First, Goal::Last] {
+            assert_eq!(Outcome::Impossible, Search::new(&boards).plan(0, goal));
+            match search.plan(0, goal) {
+                Outcome::Found { draws, optimal } => {
+                    assert!(optimal);
+                    check(&boards, &patterns, 0, goal, &draws);
+                }
+                outcome => panic!("board 0 can win with the diagonals, got {:?}", outcome),
+            }
+        }
+    }
+
+    #[test]
+    fn test_brute_force() {
+        let mut generator = Generator::new(7);
+        let mut impossible = 0;
+        for _ in 0..20 {
+            let numbers = generator.boards(4, 2, 9);
+            let boards = make_boards(&numbers, 2);
+            let search = Search::new(&boards);
+            for board in 0..boards.len() {
+                for goal in [Goal::First, Goal::Last] {
+                    let expected = brute_force(&boards, board, goal);
+                    match search.plan(board, goal) {
+                        Outcome::Found { draws, optimal } => {
+                            assert!(optimal);
+                            check(&boards, &WinPattern::STANDARD, board, goal, &draws);
+                            assert_eq!(expected, Some(draws.len()), "{:?}", numbers);
+                        }
+                        Outcome::Impossible => {
+                            assert_eq!(None, expected, "{:?}", numbers);
+                            impossible += 1;
+                        }
+                        Outcome::GaveUp => panic!("small search gave up"),
+                    }
+                }
+            }
+        }
+        assert!(impossible > 0);
+    }
+
+    #[test]
+    fn test_gave_up() {
+        let mut generator = Generator::new(3);
+        let numbers = generator.boards(30, 5, 100);
+        let boards = make_boards(&numbers, 5);
+        let mut search = Search::new(&boards);
+        search.max_nodes = 1;
+        match search.plan(0, Goal::Last) {
+            Outcome::GaveUp => (),
+            Outcome::Found { draws, optimal } => {
+                assert!(!optimal);
+                check(&boards, &WinPattern::STANDARD, 0, Goal::Last, &draws);
+            }
+            Outcome::Impossible => panic!("search can't prove anything with a single node"),
+        }
+    }
+}
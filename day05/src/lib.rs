@@ -0,0 +1,350 @@
+//! Day 5: Hydrothermal Venture, counts the points where the lines of vents overlap
+
+use aoc_common::{Answer, Location, Solver};
+use regex::{self, Regex};
+use std::cmp::{max, min};
+use std::fmt;
+use std::io;
+
+aoc_common::parse_error! {
+    /// Errors found parsing the lines of vents
+    pub enum ParseError {
+        /// A line that does not have the form "x1,y1 -> x2,y2"
+        InvalidSegment(Location),
+        /// A coordinate too big to fit in the map
+        InvalidCoordinate(Location),
+    }
+
+    fn fmt(&self, f) {
+        ParseError::InvalidSegment(loc) => {
+            write!(f, "{}: invalid segment '{}'", loc, loc.token)
+        }
+        ParseError::InvalidCoordinate(loc) => {
+            write!(f, "{}: invalid coordinate '{}'", loc, loc.token)
+        }
+    }
+}
+
+/// Point of the ocean floor
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Point {
+    x: u32,
+    y: u32,
+}
+
+impl Point {
+    pub fn new(x: u32, y: u32) -> Point {
+        Point { x, y }
+    }
+
+    pub fn x(&self) -> u32 {
+        self.x
+    }
+
+    pub fn y(&self) -> u32 {
+        self.y
+    }
+}
+
+/// Start and end points of a line of vents
+pub type Segment = (Point, Point);
+
+/// All the points covered by a line of vents
+pub struct Line {
+    points: Vec<Point>,
+}
+
+impl fmt::Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:3}, {:3}", self.x, self.y)
+    }
+}
+
+impl fmt::Display for Line {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut tmp_string = String::new();
+        for (i, point) in self.points.iter().enumerate() {
+            let sep = if i % 5 == 4 {
+                String::from("\n")
+            } else {
+                String::from(" ")
+            };
+            tmp_string.push_str(format!("{}{}", point, sep.as_str()).as_str());
+        }
+        write!(f, "{}", tmp_string)
+    }
+}
+
+impl Line {
+    /// Line from `start` to `end`, both included, which must be horizontal, vertical or diagonal
+    pub fn new(start: &Point, end: &Point) -> Line {
+        let mut line = Line { points: vec![] };
+        line.initialize_points_in_line(start, end);
+        line
+    }
+
+    pub fn points(&self) -> &[Point] {
+        &self.points
+    }
+
+    fn initialize_points_in_line(&mut self, start: &Point, end: &Point) {
+        let startx = min(start.x, end.x);
+        let starty = min(start.y, end.y);
+
+        let endx = max(start.x, end.x);
+        let endy = max(start.y, end.y);
+
+        let number_of_points = max(endx - startx + 1, endy - starty + 1) as usize;
+
+        let x_idx: Vec<u32> = if startx == endx {
+            vec![startx; number_of_points]
+        } else {
+            if start.x < end.x {
+                (start.x..=end.x).collect::<Vec<u32>>()
+            } else {
+                (end.x..=start.x).rev().collect::<Vec<u32>>()
+            }
+        };
+
+        let y_idx: Vec<u32> = if starty == endy {
+            vec![starty; number_of_points]
+        } else {
+            if start.y < end.y {
+                (start.y..=end.y).collect::<Vec<u32>>()
+            } else {
+                (end.y..=start.y).rev().collect::<Vec<u32>>()
+            }
+        };
+
+        for (x, y) in x_idx.iter().zip(y_idx.iter()) {
+            self.points.push(Point { x: *x, y: *y });
+        }
+    }
+}
+
+/* Parse a line like "0,9 -> 5,9", line_no starts at 1 */
+fn parse_points_string(re: &Regex, line_no: usize, pair_str: &str) -> Result<Segment, ParseError> {
+    let caps = match re.captures(pair_str) {
+        Some(caps) => caps,
+        None => {
+            let token = pair_str.trim();
+            return Err(ParseError::InvalidSegment(Location::in_line(
+                line_no, pair_str, token,
+            )));
+        }
+    };
+
+    let mut coordinates = [0u32; 4];
+    for (i, coordinate) in coordinates.iter_mut().enumerate() {
+        let token = caps.get(i + 1).map_or("", |x| x.as_str());
+        *coordinate = match token.parse::<u32>() {
+            Ok(val) => val,
+            Err(_) => {
+                return Err(ParseError::InvalidCoordinate(Location::in_line(
+                    line_no, pair_str, token,
+                )))
+            }
+        };
+    }
+
+    let [x1, y1, x2, y2] = coordinates;
+    Ok((Point { x: x1, y: y1 }, Point { x: x2, y: y2 }))
+}
+
+fn segment_regex() -> Regex {
+    Regex::new(r"^\s*(\d+),(\d+) -> (\d+),(\d+)\s*$").unwrap()
+}
+
+/// Reads one segment like "0,9 -> 5,9" per line
+pub fn parse_segments<I: io::BufRead>(reader: I) -> Result<Vec<Segment>, ParseError> {
+    let re = segment_regex();
+    let mut segments = vec![];
+
+    for (cnt, line) in reader.lines().enumerate() {
+        segments.push(parse_points_string(&re, cnt + 1, &line?)?);
+    }
+    Ok(segments)
+}
+
+/// Size of the map, as max_x and max_y, and the lines of all the segments
+pub fn make_lines(segments: &[Segment], exclude_diagonals: bool) -> (u32, u32, Vec<Line>) {
+    let mut max_x = 0;
+    let mut max_y = 0;
+    let mut lines = vec![];
+
+    for (start, end) in segments {
+        if max(start.x, end.x) > max_x {
+            max_x = max(start.x, end.x);
+        }
+        if max(start.y, end.y) > max_y {
+            max_y = max(start.y, end.y);
+        }
+
+        if exclude_diagonals && (start.x != end.x) && (start.y != end.y) {
+            continue;
+        }
+        lines.push(Line::new(start, end));
+    }
+
+    (max_x + 1, max_y + 1, lines)
+}
+
+/// Number of points of the map covered by two or more lines
+pub fn calculate_overlaps(max_x: u32, max_y: u32, lines: &Vec<Line>) -> u32 {
+    let mut space: Vec<u32> = vec![0u32; (max_x * max_y).try_into().unwrap()];
+
+    let mut count_overlaps = 0;
+    for line in lines {
+        for point in &line.points {
+            let pos: usize = (point.x + max_y * point.y).try_into().unwrap();
+            space[pos] += 1;
+            if space[pos] == 2 {
+                count_overlaps += 1;
+            }
+        }
+    }
+    count_overlaps
+}
+
+/// Solver of the day, part 1 only considers horizontal and vertical lines and part 2
+/// adds the diagonals
+pub struct Day05;
+
+impl Solver for Day05 {
+    const DAY: u32 = 5;
+
+    type Input = Vec<Segment>;
+
+    fn parse<R: io::BufRead>(&self, reader: R) -> aoc_common::Result<Vec<Segment>> {
+        Ok(parse_segments(reader)?)
+    }
+
+    fn part1(&self, segments: &Vec<Segment>) -> aoc_common::Result<Answer> {
+        let (max_x, max_y, lines) = make_lines(segments, true);
+        Ok(calculate_overlaps(max_x, max_y, &lines).into())
+    }
+
+    fn part2(&self, segments: &Vec<Segment>) -> aoc_common::Result<Answer> {
+        let (max_x, max_y, lines) = make_lines(segments, false);
+        Ok(calculate_overlaps(max_x, max_y, &lines).into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+    const F: &[u8] = b"0,9 -> 5,9\n8,0 -> 0,8\n9,4 -> 3,4\n2,2 -> 2,1\n7,0 -> 7,4\n6,4 -> 2,0\n0,9 -> 2,9\n3,4 -> 1,4\n0,0 -> 8,8\n5,5 -> 8,2" as &[u8];
+
+    #[test]
+    fn integration_test_solution2() {
+        let reader = io::BufReader::new(F);
+        let (max_x, max_y, lines) = make_lines(&parse_segments(reader).unwrap(), false);
+        let overlaps = calculate_overlaps(max_x, max_y, &lines);
+        assert_eq!(10, max_x);
+        assert_eq!(10, max_y);
+        assert_eq!(10, lines.len());
+
+        let mut lines_iterator = lines.iter();
+
+        let line = lines_iterator.next().unwrap();
+        for (i, point) in line.points.iter().enumerate() {
+            assert_eq!(point.y, 9);
+            assert_eq!(i as u32, point.x);
+        }
+
+        assert_eq!(12, overlaps);
+    }
+
+    #[test]
+    fn integration_test_solution1() {
+        let reader = io::BufReader::new(F);
+        let (max_x, max_y, lines) = make_lines(&parse_segments(reader).unwrap(), true);
+        let overlaps = calculate_overlaps(max_x, max_y, &lines);
+        assert_eq!(10, max_x);
+        assert_eq!(10, max_y);
+        assert_eq!(6, lines.len());
+
+        let mut lines_iterator = lines.iter();
+
+        let line = lines_iterator.next().unwrap();
+        for (i, point) in line.points.iter().enumerate() {
+            assert_eq!(point.y, 9);
+            assert_eq!(i as u32, point.x);
+        }
+
+        assert_eq!(5, overlaps);
+    }
+
+    #[test]
+    fn test_initialize_points_in_line() {
+        let mut line = Line { points: vec![] };
+
+        line.initialize_points_in_line(&Point { x: 0, y: 0 }, &Point { x: 3, y: 0 });
+        assert_eq!(4, line.points.len());
+        for (i, point) in line.points.iter().enumerate() {
+            assert_eq!(point.x, i as u32);
+            assert_eq!(point.y, 0);
+        }
+
+        let mut line = Line { points: vec![] };
+        line.initialize_points_in_line(&Point { x: 3, y: 0 }, &Point { x: 0, y: 0 });
+        assert_eq!(4, line.points.len());
+        for (i, point) in line.points.iter().enumerate() {
+            assert_eq!(point.x, 3 - i as u32);
+            assert_eq!(point.y, 0);
+        }
+
+        let mut line = Line { points: vec![] };
+        line.initialize_points_in_line(&Point { x: 0, y: 3 }, &Point { x: 0, y: 0 });
+        assert_eq!(4, line.points.len());
+        for (i, point) in line.points.iter().enumerate() {
+            assert_eq!(point.x, 0);
+            assert_eq!(point.y, 3 - i as u32);
+        }
+
+        let mut line = Line { points: vec![] };
+        line.initialize_points_in_line(&Point { x: 0, y: 0 }, &Point { x: 0, y: 3 });
+        assert_eq!(4, line.points.len());
+        for (i, point) in line.points.iter().enumerate() {
+            assert_eq!(point.x, 0);
+            assert_eq!(point.y, i as u32);
+        }
+
+        let mut line = Line { points: vec![] };
+        line.initialize_points_in_line(&Point { x: 3, y: 3 }, &Point { x: 3, y: 3 });
+        assert_eq!(1, line.points.len());
+    }
+
+    #[test]
+    fn test_parse_points_string() {
+        let test_str = "0,9 -> 5,9";
+
+        let (start, end) = parse_points_string(&segment_regex(), 1, test_str).unwrap();
+
+        assert_eq!(start.x, 0);
+        assert_eq!(start.y, 9);
+        assert_eq!(end.x, 5);
+        assert_eq!(end.y, 9);
+    }
+
+    #[test]
+    fn test_parse_points_string_errors() {
+        let re = segment_regex();
+
+        match parse_points_string(&re, 4, "0,9 -> 5") {
+            Err(ParseError::InvalidSegment(loc)) => {
+                assert_eq!(Location::new(4, 1, "0,9 -> 5"), loc)
+            }
+            _ => panic!("expected an invalid segment"),
+        }
+
+        match parse_points_string(&re, 2, "0,9 -> 99999999999,9") {
+            Err(ParseError::InvalidCoordinate(loc)) => {
+                assert_eq!(Location::new(2, 8, "99999999999"), loc)
+            }
+            _ => panic!("expected an invalid coordinate"),
+        }
+    }
+}
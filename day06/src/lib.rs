@@ -0,0 +1,313 @@
+//! Day 6: Lanternfish, counts the lanternfish after a number of days
+
+use aoc_common::count::add_to;
+use aoc_common::{Answer, Count, Location, Result, Solver};
+use std::io::BufRead;
+
+aoc_common::parse_error! {
+    /// Errors found parsing the timers of the fish
+    pub enum ParseError {
+        /// A timer that is not a number between 0 and 8
+        InvalidAge(Location),
+    }
+
+    fn fmt(&self, f) {
+        ParseError::InvalidAge(loc) => write!(f, "{}: invalid age '{}'", loc, loc.token),
+    }
+}
+
+type ParseResult<T> = std::result::Result<T, ParseError>;
+
+#[cfg(test)]
+struct Fish {
+    counter: u32,
+    first_generation: bool,
+}
+
+#[cfg(test)]
+impl Fish {
+    fn new(counter: u32) -> Fish {
+        Fish {
+            counter,
+            first_generation: true,
+        }
+    }
+
+    fn iterate(&mut self) {
+        self.counter = if self.counter == 0 {
+            self.first_generation = false;
+            6
+        } else {
+            self.counter - 1u32
+        }
+    }
+}
+
+/// School of lanternfish, grouped by the days left until they create a new fish
+///
+/// The fish are counted with `C`, `u64` overflows after about 440 days while `BigUint`,
+/// with the `bigint` feature, can count any number of days.
+pub struct Bank<C = u64> {
+    ages: Vec<C>,
+    generation_counter: u32,
+}
+
+impl Bank {
+    /// School with one fish per timer in `age_list`, counted with `u64`
+    ///
+    /// Panics if a timer is greater than 8
+    pub fn new(age_list: &[u32]) -> Bank {
+        Bank::from_ages(age_list)
+    }
+}
+
+impl<C: Count> Bank<C> {
+    /// School with one fish per timer in `age_list`
+    ///
+    /// Panics if a timer is greater than 8
+    pub fn from_ages(age_list: &[u32]) -> Bank<C> {
+        let mut counts = [0_u64; 9];
+        for idx in age_list {
+            counts[*idx as usize] += 1
+        }
+        Bank {
+            ages: counts.iter().map(|n| C::from_u64(*n)).collect(),
+            generation_counter: 0u32,
+        }
+    }
+
+    /// Advances the school one day, fails if the fish can't be counted with `C`
+    pub fn iterate(&mut self) -> Result<()> {
+        // i <= i+1
+        // 0 -> 6 & 8
+        self.ages.rotate_left(1);
+        let zero_val = self.ages[8].clone();
+        add_to(&mut self.ages[6], &zero_val, "number of fish")?;
+
+        self.generation_counter += 1;
+        Ok(())
+    }
+
+    /// Days the school has advanced
+    pub fn generation(&self) -> u32 {
+        self.generation_counter
+    }
+
+    /// Number of fish in the school, fails if it can't be counted with `C`
+    pub fn sum(&self) -> Result<C> {
+        let mut total = C::zero();
+        for i in &self.ages {
+            add_to(&mut total, i, "number of fish")?;
+        }
+        Ok(total)
+    }
+}
+
+#[cfg(test)]
+fn get_children_start_times_seed(max_time: u32, start_time: u32, start_value: u32) -> Vec<u32> {
+    let mut time = start_time + start_value + 1;
+    let mut vec = vec![];
+    while time <= max_time {
+        vec.push(time);
+        time += 7;
+    }
+    vec
+}
+
+#[cfg(test)]
+fn count_fishes(max_time: u32, start_time: u32, start_value: u32) -> usize {
+    let mut vec = vec![(start_time, start_value)];
+    let mut counter = 0;
+    while let Some(node) = vec.pop() {
+        counter += 1;
+        for ch in get_children_start_times_seed(max_time, node.0, node.1) {
+            vec.push((ch, 8));
+        }
+    }
+    counter
+}
+
+/// Number of fishes after the given days, fails if they can't be counted with `C`
+///
+/// `bank_size_after::<BigUint>(&ages, 10_000)` counts them with arbitrary precision.
+pub fn bank_size_after<C: Count>(ages: &[u32], days: u32) -> Result<C> {
+    let mut bank = Bank::<C>::from_ages(ages);
+    while bank.generation_counter < days {
+        bank.iterate()?
+    }
+    bank.sum()
+}
+
+/// Reads the comma separated timers of the fish
+pub fn parse_ages<I: BufRead>(reader: I) -> ParseResult<Vec<u32>> {
+    let mut ages = vec![];
+
+    for (cnt, line) in reader.lines().enumerate() {
+        let lstr = line?;
+        for n in lstr.split(',').map(|n| n.trim()).filter(|n| !n.is_empty()) {
+            match n.parse::<u32>() {
+                Ok(age) if age <= 8 => ages.push(age),
+                _ => return Err(ParseError::InvalidAge(Location::in_line(cnt + 1, &lstr, n))),
+            }
+        }
+    }
+    Ok(ages)
+}
+
+/// Solver of the day, part 1 counts the fish after 80 days and part 2 after 256 days
+pub struct Day06;
+
+impl Solver for Day06 {
+    const DAY: u32 = 6;
+
+    type Input = Vec<u32>;
+
+    fn parse<R: BufRead>(&self, reader: R) -> Result<Vec<u32>> {
+        Ok(parse_ages(reader)?)
+    }
+
+    fn part1(&self, ages: &Vec<u32>) -> Result<Answer> {
+        Ok(bank_size_after::<u64>(ages, 80)?.into())
+    }
+
+    fn part2(&self, ages: &Vec<u32>) -> Result<Answer> {
+        Ok(bank_size_after::<u64>(ages, 256)?.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_common::Error;
+
+    #[test]
+    fn test_count_fishes() {
+        let m = count_fishes(18, 0, 3);
+        assert_eq!(5, m);
+
+        let mut counter = 0;
+        for i in [3, 4, 3, 1, 2] {
+            counter += count_fishes(18, 0, i)
+        }
+        assert_eq!(26, counter);
+
+        counter = 0;
+        for i in [3, 4, 3, 1, 2] {
+            counter += count_fishes(80, 0, i)
+        }
+        assert_eq!(5934, counter);
+    }
+
+
+    #[test]
+    fn test_get_children_start_times_seed() {
+        let start_times = get_children_start_times_seed(18, 0, 3);
+
+        assert_eq!(3, start_times.len());
+        for (i, t) in start_times.iter().zip([4u32, 11, 18]) {
+            assert_eq!(*i, t);
+        }
+
+        let start_times = get_children_start_times_seed(18, 0, 4);
+
+        assert_eq!(2, start_times.len());
+        for (i, t) in start_times.iter().zip([5u32, 12]) {
+            assert_eq!(*i, t);
+        }
+    }
+
+    #[test]
+    fn test_bank_iterate() {
+        let mut bank = Bank::new(&[0, 1, 2, 3, 4, 5, 6, 7, 8]);
+
+        assert_eq!(0, bank.generation_counter);
+
+        bank.iterate().unwrap();
+        for (i, val) in [1u64, 1, 1, 1, 1, 1, 2, 1, 1].iter().enumerate() {
+            assert_eq!(1, bank.generation_counter);
+            assert_eq!(*val, bank.ages[i], "index: {}", i)
+        }
+
+
+        let mut bank =  Bank::new(&[3, 4, 3, 1, 2]);
+        for _ in 0..80 {
+            bank.iterate().unwrap()
+        }
+        assert_eq!(5934, bank.sum().unwrap());
+
+    }
+
+    #[test]
+    fn test_bank_new() {
+        let bank = Bank::new(&[0, 1, 2, 3, 4, 5, 6, 7, 8]);
+
+        assert_eq!(0, bank.generation_counter);
+
+        for age in bank.ages.iter() {
+            assert_eq!(1, *age);
+        }
+    }
+
+    #[test]
+    fn test_fish_new() {
+        let fish = Fish::new(5);
+        assert_eq!(5, fish.counter);
+        assert!(fish.first_generation);
+    }
+
+    #[test]
+    fn test_fish_iterate() {
+        let mut fish = Fish::new(0);
+
+        assert_eq!(0, fish.counter);
+        assert!(fish.first_generation);
+
+        fish.iterate();
+        assert_eq!(6, fish.counter);
+        assert!(!fish.first_generation);
+
+        for i in (0..6).rev() {
+            fish.iterate();
+            assert_eq!(i, fish.counter);
+            assert!(!fish.first_generation);
+        }
+        fish.iterate();
+        assert_eq!(6, fish.counter);
+        assert!(!fish.first_generation);
+    }
+
+    #[test]
+    fn test_parse_ages() {
+        let ages = parse_ages("3,4,3,1,2\n".as_bytes()).unwrap();
+        assert_eq!(vec![3, 4, 3, 1, 2], ages);
+
+        match parse_ages("3,4, 9,1".as_bytes()) {
+            Err(ParseError::InvalidAge(loc)) => assert_eq!(Location::new(1, 6, "9"), loc),
+            _ => panic!("expected an invalid age"),
+        }
+    }
+
+    #[test]
+    fn test_bank_size_after() {
+        let ages = [3, 4, 3, 1, 2];
+        assert_eq!(26984457539, bank_size_after::<u64>(&ages, 256).unwrap());
+
+        match bank_size_after::<u64>(&ages, 1000) {
+            Err(Error::Overflow(_)) => (),
+            _ => panic!("expected an overflow"),
+        }
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn test_bank_size_after_bigint() {
+        use aoc_common::BigUint;
+
+        let ages = [3, 4, 3, 1, 2];
+        let fish = bank_size_after::<BigUint>(&ages, 256).unwrap();
+        assert_eq!(BigUint::from(26984457539_u64), fish);
+
+        let fish = bank_size_after::<BigUint>(&ages, 1000).unwrap();
+        assert!(fish > BigUint::from(u64::MAX));
+    }
+}
@@ -0,0 +1,237 @@
+//! Day 7: The Treachery of Whales, finds the position where aligning the crabs costs the
+//! least fuel
+
+use aoc_common::{Answer, Location, Result, Solver};
+use log::debug;
+use std::io::BufRead;
+
+aoc_common::parse_error! {
+    /// Errors found parsing the positions of the crabs
+    pub enum ParseError {
+        /// The input has no positions
+        Empty,
+        /// A position that is not an integer
+        InvalidPosition(Location),
+    }
+
+    fn fmt(&self, f) {
+        ParseError::Empty => write!(f, "no crab positions found"),
+        ParseError::InvalidPosition(loc) => {
+            write!(f, "{}: invalid position '{}'", loc, loc.token)
+        }
+    }
+}
+
+type ParseResult<T> = std::result::Result<T, ParseError>;
+
+#[cfg(test)]
+fn avg(ns: &[i64]) -> i64 {
+    let mut c = 0;
+    ns.iter().for_each(|n| c += n);
+    c / ns.len() as i64
+}
+
+/// Fuel needed to move all the crabs to `pos` when each step costs 1
+pub fn calculate_cost(ns: &[i64], pos: i64) -> i64 {
+    ns.iter().map(|x| (x - pos).abs()).sum()
+}
+
+fn sum_series(n: i64) -> i64 {
+    n*(n+1)/2
+}
+
+/// Fuel needed to move all the crabs to `pos` when each step costs one more than the previous
+pub fn calculate_crab_cost(ns: &[i64], pos: i64) -> i64 {
+    ns.iter().map(|x| sum_series( (x-pos).abs() ) ).sum()
+}
+
+// returns index of the max and value
+fn max(ns: &[i64]) -> (usize, i64) {
+    let mut max_val = 0;
+    let mut max_index: usize = 0;
+    for (i, n) in ns.iter().enumerate() {
+        if *n > max_val {
+            max_val = *n;
+            max_index = i;
+        }
+    }
+    (max_index, max_val)
+}
+
+#[cfg(test)]
+fn min(ns: &[i64]) -> i64 {
+    let mut min_val = 0;
+    for n in ns.iter() {
+        if *n < min_val {
+            min_val = *n;
+        }
+    }
+    min_val
+}
+
+/// Cheapest position to align the crabs and its cost, each step costs 1
+pub fn calculate_min_cost(ns: &[i64]) -> (usize, i64) {
+    let mut min_cost = calculate_cost(ns, 0_i64);
+    let mut min_pos = 0;
+    let (_, max_pos) = max(ns);
+
+    for i in 1..=max_pos {
+        let cost = calculate_cost(ns, i);
+        if cost <= min_cost {
+            min_cost = cost;
+            min_pos = i as usize;
+        }
+    }
+    debug!("{} crabs, cheapest position {} costs {}", ns.len(), min_pos, min_cost);
+    (min_pos, min_cost)
+}
+
+/// Cheapest position to align the crabs and its cost, each step costs one more than the
+/// previous
+pub fn calculate_min_crab_cost(ns: &[i64]) -> (usize, i64) {
+    let mut min_cost = calculate_crab_cost(ns, 0_i64);
+    let mut min_pos = 0;
+    let (_, max_pos) = max(ns);
+
+    for i in 1..=max_pos {
+        let cost = calculate_crab_cost(ns, i);
+        if cost <= min_cost {
+            min_cost = cost;
+            min_pos = i as usize;
+        }
+    }
+    debug!("{} crabs, cheapest position {} costs {}", ns.len(), min_pos, min_cost);
+    (min_pos, min_cost)
+}
+
+/// Reads the comma separated positions of the crabs from the first line
+pub fn parse_positions<I: BufRead>(reader: I) -> ParseResult<Vec<i64>> {
+    let line = match reader.lines().next() {
+        Some(line) => line?,
+        None => return Err(ParseError::Empty),
+    };
+
+    let mut positions = vec![];
+    for x in line.split(',').map(|x| x.trim()) {
+        match x.parse::<i64>() {
+            Ok(pos) => positions.push(pos),
+            Err(_) => return Err(ParseError::InvalidPosition(Location::in_line(1, &line, x))),
+        }
+    }
+    Ok(positions)
+}
+
+/// Solver of the day, part 1 uses constant step costs and part 2 increasing step costs
+pub struct Day07;
+
+impl Solver for Day07 {
+    const DAY: u32 = 7;
+
+    type Input = Vec<i64>;
+
+    fn parse<R: BufRead>(&self, reader: R) -> Result<Vec<i64>> {
+        Ok(parse_positions(reader)?)
+    }
+
+    fn part1(&self, positions: &Vec<i64>) -> Result<Answer> {
+        let (_, min_cost) = calculate_min_cost(positions);
+        Ok(min_cost.into())
+    }
+
+    fn part2(&self, positions: &Vec<i64>) -> Result<Answer> {
+        let (_, min_cost) = calculate_min_crab_cost(positions);
+        Ok(min_cost.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_sum_series() {
+        assert_eq!(10, sum_series(4));
+        assert_eq!(15, sum_series(5));
+        assert_eq!(0, sum_series(0));
+        assert_eq!(1, sum_series(1));
+        assert_eq!(3, sum_series(2));
+        assert_eq!(6, sum_series(3));
+
+    }
+
+
+
+    #[test]
+    fn test_calculate_min_crab_cost() {
+        let ns = &[16i64, 1, 2, 0, 4, 2, 7, 1, 2, 14];
+        let (p, m) = calculate_min_crab_cost(ns);
+        assert_eq!(168, m);
+        assert_eq!(5, p);
+    }
+
+
+    #[test]
+    fn test_calculate_min_cost() {
+        let ns = &[16i64, 1, 2, 0, 4, 2, 7, 1, 2, 14];
+        let (p, m) = calculate_min_cost(ns);
+        assert_eq!(37, m);
+        assert_eq!(2, p);
+    }
+
+    #[test]
+    fn test_calculate_avg() {
+        let ns = &[16i64, 1, 2, 0, 4, 2, 7, 1, 2, 14];
+        let m = avg(ns);
+        assert_eq!(4, m);
+    }
+
+    #[test]
+    fn test_max() {
+        let ns = &[16i64, 1, 2, 0, 4, 2, 7, 1, 2, 14];
+        assert_eq!(0, max(ns).0);
+        assert_eq!(16, max(ns).1);
+    }
+
+    #[test]
+    fn test_min() {
+        let ns = &[16i64, 1, 2, 0, 4, 2, 7, 1, 2, 14];
+        assert_eq!(0, min(ns));
+
+    }
+
+    #[test]
+    fn test_calculate_cost() {
+        let ns: &[i64] = &[16i64, 1, 2, 0];
+        assert_eq![ns.iter().sum::<i64>(), calculate_cost(ns, 0)];
+
+        let ns: &[i64] = &[16i64, 1, 2, 0];
+        assert_eq![8 + 7 + 6 + 8, calculate_cost(ns, 8)]
+    }
+
+    #[test]
+    fn test_calculate_crab_cost() {
+
+        let ns = &[16i64, 16, 16];
+        let c = calculate_crab_cost(ns, 16);
+        assert_eq!(0, c);
+
+        let c = calculate_crab_cost(ns, 15);
+        assert_eq!(3, c);
+
+
+
+
+    }
+
+    #[test]
+    fn test_parse_positions() {
+        let positions = parse_positions("16,1,2\n".as_bytes()).unwrap();
+        assert_eq!(vec![16, 1, 2], positions);
+
+        assert!(matches!(parse_positions("".as_bytes()), Err(ParseError::Empty)));
+        match parse_positions("16,1,,2".as_bytes()) {
+            Err(ParseError::InvalidPosition(loc)) => assert_eq!(Location::new(1, 6, ""), loc),
+            _ => panic!("expected an invalid position"),
+        }
+    }
+}
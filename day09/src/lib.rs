@@ -0,0 +1,286 @@
+//! Day 9: Smoke Basin, finds the low points of the cave floor and the basins around them
+
+use aoc_common::{Answer, Grid, Result, Solver};
+use std::collections::VecDeque;
+use std::io::BufRead;
+
+/// Height map of the cave floor
+pub type Data = Grid<u8>;
+
+/// Location in the height map and its height
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct Point {
+    pub i: usize,
+    pub j: usize,
+    pub val: u8,
+}
+
+/// Reads the height map, one digit per location
+pub fn parse_input<I: BufRead>(reader: I) -> Result<Data> {
+    Ok(Grid::parse_digits(reader)?)
+}
+
+/// Locations lower than all their horizontal and vertical neighbours
+pub fn find_low_points(data: &Data) -> Vec<Point> {
+    let mut low_points: Vec<Point> = vec![];
+
+    for ((i, j), val) in data.indexed_iter() {
+        if data.neighbours4((i, j)).all(|n| *val < data[n]) {
+            low_points.push(Point { i, j, val: *val });
+        }
+    }
+    low_points
+}
+
+/// Sum of the risk levels, one more than the height, of the low points
+pub fn sum_risk_level(low_points: &[Point]) -> usize {
+    low_points.iter().map(|p| 1 + p.val as usize).sum()
+}
+
+fn find_neighbours(data: &Data, point: Point) -> Vec<Point> {
+    data.neighbours4((point.i, point.j))
+        .map(|(i, j)| Point {
+            i,
+            j,
+            val: data[(i, j)],
+        })
+        .collect()
+}
+
+/// Locations flowing down to `point`, every location not of height 9 belongs to a basin
+pub fn find_basin(data: &Data, point: Point) -> Vec<Point> {
+    let mut points = vec![];
+    let mut unvisited: VecDeque<Point> = VecDeque::new();
+    unvisited.push_back(point);
+
+    while let Some(p) = unvisited.pop_front() {
+        if !points.contains(&p) {
+            points.push(p);
+
+            for n in find_neighbours(data, p) {
+                if n.val != 9 {
+                    unvisited.push_back(n)
+                }
+            }
+        }
+    }
+    points
+}
+
+/// Solver of the day, part 1 sums the risk levels and part 2 multiplies the three largest
+/// basins
+pub struct Day09;
+
+impl Solver for Day09 {
+    const DAY: u32 = 9;
+
+    type Input = Data;
+
+    fn parse<R: BufRead>(&self, reader: R) -> Result<Data> {
+        parse_input(reader)
+    }
+
+    fn part1(&self, data: &Data) -> Result<Answer> {
+        let low_points = find_low_points(data);
+        Ok(sum_risk_level(&low_points).into())
+    }
+
+    fn part2(&self, data: &Data) -> Result<Answer> {
+        let mut basin_vec = vec![];
+        for p in find_low_points(data) {
+            basin_vec.push(find_basin(data, p))
+        }
+        basin_vec.sort_by_key(|a| a.len());
+
+        let prod: usize = basin_vec.iter().rev().take(3).map(|v| v.len()).product();
+        Ok(prod.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io;
+
+    const F: &[u8] = b"2199943210\n3987894921\n9856789892\n8767896789\n9899965678" as &[u8];
+
+    #[test]
+    fn test_find_basin() {
+        let reader = io::BufReader::new(F);
+        let data = parse_input(reader).unwrap();
+
+        let basin1 = find_basin(&data, Point { i: 0, j: 0, val: 2 });
+        assert_eq!(3, basin1.len());
+
+        let basin2 = find_basin(&data, Point { i: 0, j: 9, val: 0 });
+        assert_eq!(9, basin2.len());
+
+        let basin3 = find_basin(&data, Point { i: 2, j: 2, val: 5 });
+        assert_eq!(14, basin3.len());
+
+        let basin4 = find_basin(&data, Point { i: 4, j: 6, val: 5 });
+        assert_eq!(9, basin4.len());
+
+        let mut vec = [basin1, basin2, basin3, basin4];
+        vec.sort_by_key(|a| a.len());
+
+        let mut i = 0;
+        let mut prod = 1;
+        for v in vec.iter().rev() {
+            prod *= v.len();
+            i += 1;
+            if i == 3 {
+                break;
+            }
+        }
+        assert_eq!(1134, prod);
+    }
+
+    #[test]
+    fn test_find_neighbours() {
+        let reader = io::BufReader::new(F);
+        let data = parse_input(reader).unwrap();
+
+        let neighbours = find_neighbours(&data, Point { i: 0, j: 0, val: 2 });
+        assert_eq!(2, neighbours.len());
+        assert_eq!(
+            (1, 0, 3),
+            (neighbours[0].i, neighbours[0].j, neighbours[0].val)
+        );
+        assert_eq!(
+            (0, 1, 1),
+            (neighbours[1].i, neighbours[1].j, neighbours[1].val)
+        );
+
+        let neighbours = find_neighbours(&data, Point { i: 0, j: 9, val: 0 });
+
+        assert_eq!(2, neighbours.len());
+        assert_eq!(
+            (1, 9, 1),
+            (neighbours[0].i, neighbours[0].j, neighbours[0].val)
+        );
+        assert_eq!(
+            (0, 8, 1),
+            (neighbours[1].i, neighbours[1].j, neighbours[1].val)
+        );
+
+        let neighbours = find_neighbours(&data, Point { i: 1, j: 0, val: 3 });
+
+        assert_eq!(3, neighbours.len());
+        assert_eq!(
+            (0, 0, 2),
+            (neighbours[0].i, neighbours[0].j, neighbours[0].val)
+        );
+        assert_eq!(
+            (2, 0, 9),
+            (neighbours[1].i, neighbours[1].j, neighbours[1].val)
+        );
+        assert_eq!(
+            (1, 1, 9),
+            (neighbours[2].i, neighbours[2].j, neighbours[2].val)
+        );
+
+        let neighbours = find_neighbours(&data, Point { i: 1, j: 9, val: 1 });
+
+        assert_eq!(3, neighbours.len());
+        assert_eq!(
+            (0, 9, 0),
+            (neighbours[0].i, neighbours[0].j, neighbours[0].val)
+        );
+        assert_eq!(
+            (2, 9, 2),
+            (neighbours[1].i, neighbours[1].j, neighbours[1].val)
+        );
+        assert_eq!(
+            (1, 8, 2),
+            (neighbours[2].i, neighbours[2].j, neighbours[2].val)
+        );
+
+        let neighbours = find_neighbours(&data, Point { i: 1, j: 1, val: 9 });
+
+        assert_eq!(4, neighbours.len());
+        assert_eq!(
+            (0, 1, 1),
+            (neighbours[0].i, neighbours[0].j, neighbours[0].val)
+        );
+        assert_eq!(
+            (2, 1, 8),
+            (neighbours[1].i, neighbours[1].j, neighbours[1].val)
+        );
+        assert_eq!(
+            (1, 0, 3),
+            (neighbours[2].i, neighbours[2].j, neighbours[2].val)
+        );
+        assert_eq!(
+            (1, 2, 8),
+            (neighbours[3].i, neighbours[3].j, neighbours[3].val)
+        );
+
+        let neighbours = find_neighbours(&data, Point { i: 4, j: 0, val: 9 });
+
+        assert_eq!(2, neighbours.len());
+        assert_eq!(
+            (3, 0, 8),
+            (neighbours[0].i, neighbours[0].j, neighbours[0].val)
+        );
+        assert_eq!(
+            (4, 1, 8),
+            (neighbours[1].i, neighbours[1].j, neighbours[1].val)
+        );
+
+        let neighbours = find_neighbours(&data, Point { i: 4, j: 9, val: 9 });
+
+        assert_eq!(2, neighbours.len());
+        assert_eq!(
+            (3, 9, 9),
+            (neighbours[0].i, neighbours[0].j, neighbours[0].val)
+        );
+        assert_eq!(
+            (4, 8, 7),
+            (neighbours[1].i, neighbours[1].j, neighbours[1].val)
+        );
+    }
+
+    #[test]
+    fn test_sum_low_points() {
+        let reader = io::BufReader::new(F);
+        let data = parse_input(reader).unwrap();
+        let low_points = find_low_points(&data);
+
+        assert_eq!(15, sum_risk_level(&low_points));
+    }
+
+    #[test]
+    fn test_find_low_points() {
+        let reader = io::BufReader::new(F);
+        let data = parse_input(reader).unwrap();
+        let low_points = find_low_points(&data);
+
+        assert_eq!(4, low_points.len());
+        assert_eq!(
+            (0, 1, 1),
+            (low_points[0].i, low_points[0].j, low_points[0].val)
+        );
+        assert_eq!(
+            (0, 9, 0),
+            (low_points[1].i, low_points[1].j, low_points[1].val)
+        );
+        assert_eq!(
+            (2, 2, 5),
+            (low_points[2].i, low_points[2].j, low_points[2].val)
+        );
+        assert_eq!(
+            (4, 6, 5),
+            (low_points[3].i, low_points[3].j, low_points[3].val)
+        );
+    }
+
+    #[test]
+    fn test_parse_input() {
+        let reader = io::BufReader::new(F);
+        let data = parse_input(reader).unwrap();
+
+        assert_eq!(5, data.rows());
+        assert_eq!(10, data.cols());
+    }
+}
@@ -0,0 +1,363 @@
+//! Day 10: Syntax Scoring, finds the corrupted and incomplete lines of the navigation
+//! subsystem
+
+use aoc_common::{Answer, Error, Location, Result, Solver};
+use std::collections::VecDeque;
+use std::io::BufRead;
+
+aoc_common::parse_error! {
+    /// Errors found parsing the lines of the navigation subsystem
+    pub enum ParseError {
+        /// A character that is not a bracket
+        InvalidChar(Location),
+        /// A closing bracket with no bracket open
+        UnopenedBracket(Location),
+    }
+
+    fn fmt(&self, f) {
+        ParseError::InvalidChar(loc) => write!(f, "{}: invalid character '{}'", loc, loc.token),
+        ParseError::UnopenedBracket(loc) => {
+            write!(f, "{}: '{}' closes no open bracket", loc, loc.token)
+        }
+    }
+}
+
+type ParseResult<T> = std::result::Result<T, ParseError>;
+
+/// Reads the lines, only brackets are allowed and they must not close more brackets than
+/// are open
+pub fn parse_input<I: BufRead>(reader: I) -> ParseResult<Vec<String>> {
+    let mut inputs = vec![];
+    for (cnt, line_) in reader.lines().enumerate() {
+        let line = line_?;
+        let invalid = line.char_indices().find(|(_, c)| !"()[]{}<>".contains(*c));
+        if let Some((column, c)) = invalid {
+            let token = &line[column..column + c.len_utf8()];
+            return Err(ParseError::InvalidChar(Location::in_line(cnt + 1, &line, token)));
+        }
+        // only brackets are left, characters and bytes line up
+        if let Scan::Unopened(i) = scan(&line) {
+            let loc = Location::in_line(cnt + 1, &line, &line[i..i + 1]);
+            return Err(ParseError::UnopenedBracket(loc));
+        }
+        inputs.push(line);
+    }
+    Ok(inputs)
+}
+
+fn get_closing_bracket(input_bracket: char) -> Option<char> {
+    match input_bracket {
+        '[' => Some(']'),
+        '(' => Some(')'),
+        '{' => Some('}'),
+        '<' => Some('>'),
+        _ => None,
+    }
+}
+
+fn get_closing_bracket_score(closing_bracket: char) -> Option<u32> {
+    match closing_bracket {
+        ']' => Some(57),
+        ')' => Some(3),
+        '}' => Some(1197),
+        '>' => Some(25137),
+        _ => None,
+    }
+}
+
+/// Brackets of a line read up to the first one that is wrong
+enum Scan {
+    /// Every closing bracket matches, the ones still expected with the innermost last
+    Open(VecDeque<char>),
+    /// A closing bracket not matching the open one, the expected and the found ones
+    Corrupted(char, char),
+    /// Index of a closing bracket with no bracket open
+    Unopened(usize),
+    /// Index of a character that is not a bracket
+    Invalid(usize),
+}
+
+fn scan(input: &str) -> Scan {
+    let mut expected_brackets = VecDeque::<char>::new();
+
+    for (i, ib) in input.chars().enumerate() {
+        match get_closing_bracket_score(ib) {
+            Some(_) => match expected_brackets.pop_back() {
+                Some(expected) if expected == ib => continue,
+                Some(expected) => return Scan::Corrupted(expected, ib),
+                None => return Scan::Unopened(i),
+            },
+            None => match get_closing_bracket(ib) {
+                Some(c) => expected_brackets.push_back(c),
+                None => return Scan::Invalid(i),
+            },
+        }
+    }
+    Scan::Open(expected_brackets)
+}
+
+/// Error of a line that can't be scored, `i` is the index of the offending character
+fn invalid_line(input: &str, i: usize, why: &str) -> Error {
+    let c = input.chars().nth(i).unwrap_or_default();
+    Error::InvalidInput(format!("column {}: '{}' {}", i + 1, c, why))
+}
+
+/// First closing bracket not matching the open one, returns expected and found
+///
+/// Fails if a closing bracket comes with no bracket open, or on characters that are not
+/// brackets.
+pub fn find_first_error_bracket(input: &str) -> Result<Option<(char, char)>> {
+    match scan(input) {
+        Scan::Open(_) => Ok(None),
+        Scan::Corrupted(expected, found) => Ok(Some((expected, found))),
+        Scan::Unopened(i) => Err(invalid_line(input, i, "closes no open bracket")),
+        Scan::Invalid(i) => Err(invalid_line(input, i, "is not a bracket")),
+    }
+}
+
+/// Syntax error score of the corrupted lines
+pub fn get_score(inputs: &Vec<String>) -> Result<u32> {
+    let mut score = 0;
+
+    for input in inputs {
+        let error = find_first_error_bracket(input)?;
+        score += match error {
+            Some(v) => get_closing_bracket_score(v.1).unwrap_or_default(),
+            None => 0,
+        }
+    }
+    Ok(score)
+}
+
+/// Filters out the corrupted lines at the input by removing them from the vector
+/// Returns the number of lines filtered, fails as `find_first_error_bracket` does
+fn filter_out_corrupted_lines(inputs: &mut Vec<String>) -> Result<u32> {
+    let mut remove_counter = 0;
+    let mut kept = Vec::with_capacity(inputs.len());
+
+    for input in inputs.drain(..) {
+        match find_first_error_bracket(&input)? {
+            None => kept.push(input),
+            Some(_) => remove_counter += 1,
+        }
+    }
+    *inputs = kept;
+
+    Ok(remove_counter)
+}
+
+/// Autocomplete score of the missing closing brackets of a line
+pub fn get_score_missing_sequence(seq: Vec<char>) -> u64 {
+    let mut score: u64 = 0;
+    for c in seq {
+        score = 5 * score + get_closing_bracket_score2(c).unwrap()
+    }
+    score
+}
+
+
+fn get_closing_bracket_score2(closing_bracket: char) -> Option<u64> {
+    match closing_bracket {
+        ']' => Some(2),
+        ')' => Some(1),
+        '}' => Some(3),
+        '>' => Some(4),
+        _ => None,
+    }
+}
+
+/// Returns the missing closing brackets of an incomplete line, none if it is complete
+///
+/// Fails if the line is corrupted, or on the lines `find_first_error_bracket` fails on.
+pub fn get_missing_sequence(input: &str) -> Result<Vec<char>> {
+    match scan(input) {
+        Scan::Open(expected_brackets) => Ok(expected_brackets.into_iter().rev().collect()),
+        Scan::Corrupted(expected, found) => Err(Error::InvalidInput(format!(
+            "corrupted line, expected '{}' but found '{}'",
+            expected, found
+        ))),
+        Scan::Unopened(i) => Err(invalid_line(input, i, "closes no open bracket")),
+        Scan::Invalid(i) => Err(invalid_line(input, i, "is not a bracket")),
+    }
+}
+
+/// Middle autocomplete score of the incomplete lines, the corrupted ones are removed
+///
+/// Fails if no line is incomplete.
+pub fn get_middle_score(inputs: &mut Vec<String>) -> Result<u64> {
+    filter_out_corrupted_lines(inputs)?;
+    let mut scores: Vec<u64> = Vec::with_capacity(inputs.len());
+    for input in inputs.iter() {
+        let missing = get_missing_sequence(input)?;
+        if !missing.is_empty() {
+            scores.push(get_score_missing_sequence(missing));
+        }
+    }
+    if scores.is_empty() {
+        return Err(Error::NoSolution(String::from("no incomplete lines")));
+    }
+    scores.sort();
+    let middle = scores.len() / 2; // force round up
+    Ok(scores[middle])
+}
+
+/// Solver of the day, part 1 scores the corrupted lines and part 2 the incomplete ones
+pub struct Day10;
+
+impl Solver for Day10 {
+    const DAY: u32 = 10;
+
+    type Input = Vec<String>;
+
+    fn parse<R: BufRead>(&self, reader: R) -> Result<Vec<String>> {
+        Ok(parse_input(reader)?)
+    }
+
+    fn part1(&self, inputs: &Vec<String>) -> Result<Answer> {
+        Ok(get_score(inputs)?.into())
+    }
+
+    fn part2(&self, inputs: &Vec<String>) -> Result<Answer> {
+        let mut inputs = inputs.clone();
+        Ok(get_middle_score(&mut inputs)?.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io;
+
+    const F : &[u8] = b"[({(<(())[]>[[{[]{<()<>>\n[(()[<>])]({[<{<<[]>>(\n{([(<{}[<>[]}>{[]{[(<()>\n(((({<>}<{<{<>}{[]{[]{}\n[[<[([]))<([[{}[[()]]]\n[{[{({}]{}}([{[{{{}}([]\n{<[[]]>}<{[{[{[]{()[[[]\n[<(<(<(<{}))><([]([]()\n<{([([[(<>()){}]>(<<{{\n<{([{{}}[<[[[<>{}]]]>[]]" as &[u8];
+
+
+    #[test]
+    fn test_get_middle_score() {
+       let reader = io::BufReader::new(F);
+        let mut inputs = parse_input(reader).unwrap();
+
+        assert_eq!(288957, get_middle_score(&mut inputs).unwrap());
+    }
+
+    #[test]
+    fn test_get_score_missing_sequence() {
+        let reader = io::BufReader::new(F);
+        let mut inputs = parse_input(reader).unwrap();
+
+        filter_out_corrupted_lines(&mut inputs).unwrap();
+
+        let missing_seq_scores: [u64; 5] = [288957, 5566, 1480781, 995444, 294];
+        for (input, score) in inputs.iter().zip(missing_seq_scores) {
+            let missing = get_missing_sequence(input).unwrap();
+            assert_eq!(score, get_score_missing_sequence(missing));
+        }
+
+        //assert_eq!(288957, get_middle_score(inputs);
+    }
+
+    #[test]
+    fn test_get_missing_sequences() {
+        let reader = io::BufReader::new(F);
+        let mut inputs = parse_input(reader).unwrap();
+
+        filter_out_corrupted_lines(&mut inputs).unwrap();
+
+        let missing_seq_lens: [usize; 5] = [8, 6, 9, 9, 4];
+        for (counter, (input, ln)) in inputs.iter().zip(missing_seq_lens).enumerate() {
+            let missing = get_missing_sequence(input).unwrap();
+            assert_eq!(ln, missing.len());
+            if counter == 0 {
+                let vals = "}}]])})]";
+                for (ec, fc) in vals.chars().zip(missing) {
+                    assert_eq!(ec, fc);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_filter_out_corrupted_lines() {
+        let reader = io::BufReader::new(F);
+        let mut inputs = parse_input(reader).unwrap();
+        let initial_size = inputs.len();
+        let res = filter_out_corrupted_lines(&mut inputs);
+
+        assert_eq!(5, res.unwrap());
+        assert_eq!(initial_size - 5, inputs.len());
+
+        for input in inputs {
+            println!("{}", input);
+            assert_eq!(None, find_first_error_bracket(&input).unwrap());
+        }
+
+    }
+
+
+    #[test]
+    fn test_find_score() {
+        let reader = io::BufReader::new(F);
+        let inputs = parse_input(reader).unwrap();
+        let score = get_score(&inputs).unwrap();
+        assert_eq!(26397, score);
+    }
+
+    #[test]
+    fn test_find_first_error_bracket() {
+        let reader = io::BufReader::new(F);
+        let inputs = parse_input(reader).unwrap();
+        let expected = vec![None, None,
+                            Some( (']', '}') ), None,
+                            Some( (']', ')') ),
+                            Some( (')', ']') ), None,
+                            Some( ('>', ')') ),
+                            Some( (']', '>') ), None];
+
+        for (expected_values, input) in expected.iter().zip(inputs) {
+            assert_eq!(*expected_values, find_first_error_bracket(&input).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_get_closing_bracket() {
+        let inputs = "[{(<";
+        let outputs = "]})>";
+
+        for (i, o) in inputs.chars().zip(outputs.chars()) {
+            assert_eq!(Some(o), get_closing_bracket(i));
+        }
+    }
+
+    #[test]
+    fn test_parse_input_invalid_char() {
+        match parse_input(b"[({(<(())[]>[[{[]{<()<>>\n[(()[<>]) ]" as &[u8]) {
+            Err(ParseError::InvalidChar(loc)) => assert_eq!(Location::new(2, 10, " "), loc),
+            _ => panic!("expected an invalid character"),
+        }
+    }
+
+    #[test]
+    fn test_parse_input_unopened_bracket() {
+        match parse_input(b"[]\n(<>))[" as &[u8]) {
+            Err(ParseError::UnopenedBracket(loc)) => assert_eq!(Location::new(2, 5, ")"), loc),
+            _ => panic!("expected an unopened bracket"),
+        }
+        // the first error of a corrupted line comes before
+        assert!(parse_input(b"(]))" as &[u8]).is_ok());
+    }
+
+    #[test]
+    fn test_unscorable_lines() {
+        assert!(find_first_error_bracket(")").is_err());
+        assert!(find_first_error_bracket("(a)").is_err());
+        assert!(get_missing_sequence("(]").is_err());
+        assert!(get_missing_sequence("[])").is_err());
+        assert_eq!(Vec::<char>::new(), get_missing_sequence("[]").unwrap());
+
+        for mut lines in [vec![], vec![String::from("()"), String::from("(]")]] {
+            match get_middle_score(&mut lines) {
+                Err(Error::NoSolution(_)) => (),
+                other => panic!("expected no solution, got {:?}", other),
+            }
+        }
+    }
+}
@@ -0,0 +1,170 @@
+//! Day 11: Dumbo Octopus, simulates the flashing octopuses
+
+use aoc_common::{Answer, Grid, Pos, Result, Solver};
+use log::{debug, trace};
+use std::collections::VecDeque;
+use std::io::BufRead;
+
+/// Energy levels of the octopuses
+pub type Data = Grid<u8>;
+
+const FLASH_VAL: u8 = 10;
+
+/// Moves the octopuses one step forward and returns the number of flashes
+pub fn step_data(data: &mut Data) -> u32 {
+    let mut flash_list: VecDeque<Pos> = VecDeque::new();
+    let mut flash_counter: u32 = 0;
+    for pos in data.positions() {
+        data[pos] += 1;
+        if data[pos] == FLASH_VAL {
+            flash_list.extend(data.neighbours8(pos));
+        }
+    }
+
+    // every wave raises the neighbours of the octopuses that flashed in the previous one
+    let mut wave = 0;
+    while !flash_list.is_empty() {
+        wave += 1;
+        trace!("wave {}: {} octopuses raised", wave, flash_list.len());
+        for pos in std::mem::take(&mut flash_list) {
+            let val = data[pos] + 1;
+
+            if val == FLASH_VAL {
+                flash_list.extend(data.neighbours8(pos));
+            }
+
+            data[pos] = val;
+        }
+    }
+
+    for val in data.iter_mut() {
+        if *val >= FLASH_VAL {
+            *val = 0;
+            flash_counter += 1;
+        }
+    }
+    debug!("{} flashes in {} waves", flash_counter, wave);
+
+    flash_counter
+}
+
+/// Reads the energy levels, one digit per octopus
+pub fn parse_input<I: BufRead>(reader: I) -> Result<Data> {
+    Ok(Grid::parse_digits(reader)?)
+}
+
+/// Solver of the day, part 1 counts the flashes in 100 steps and part 2 finds the first step
+/// where all flash together
+pub struct Day11;
+
+impl Solver for Day11 {
+    const DAY: u32 = 11;
+
+    type Input = Data;
+
+    fn parse<R: BufRead>(&self, reader: R) -> Result<Data> {
+        parse_input(reader)
+    }
+
+    fn part1(&self, data: &Data) -> Result<Answer> {
+        let mut data = data.clone();
+        let flashes: u32 = (0..100).map(|_| step_data(&mut data)).sum();
+        Ok(flashes.into())
+    }
+
+    /// First step where all the octopuses flash at the same time
+    fn part2(&self, data: &Data) -> Result<Answer> {
+        let mut data = data.clone();
+        let all_flashes = data.len() as u32;
+        let mut step = 1_u32;
+        while step_data(&mut data) != all_flashes {
+            step += 1;
+        }
+        Ok(step.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use std::io;
+
+    #[test]
+    fn test_example() {
+        let f = b"5483143223
+2745854711
+5264556173
+6141336146
+6357385478
+4167524645
+2176841721
+6882881134
+4846848554
+5283751526" as &[u8];
+
+        let reader = io::BufReader::new(f);
+        let mut odata: Data = parse_input(reader).unwrap();
+        let data = &mut odata;
+        let mut cnt = 0;
+        for _i in 0..10 {
+            cnt += step_data(data);
+        }
+        assert_eq!(204, cnt);
+
+        for _i in 10..100 {
+            cnt += step_data(data);
+        }
+        assert_eq!(1656, cnt);
+    }
+
+    #[test]
+    fn test_step_data() {
+        let mut data: Data = Grid::from_elem(10, 10, 0);
+
+        for i in 1..FLASH_VAL {
+            let flash_counter = step_data(&mut data);
+            assert_eq!(0, flash_counter);
+            data.iter().for_each(|val| assert_eq!(i, *val))
+        }
+
+        let flash_counter = step_data(&mut data);
+        assert_eq!(data.len(), flash_counter as usize);
+        data.iter().for_each(|val| assert_eq!(0, *val))
+    }
+
+    #[test]
+    fn test_get_neighbours() {
+        let data: Data = Grid::from_elem(10, 10, 0);
+
+        let n: Vec<Pos> = data.neighbours8((0, 0)).collect();
+        let exp = [(0, 1), (1, 0), (1, 1)];
+        assert_eq!(3, n.len());
+        for (na, ne) in n.iter().zip(exp) {
+            assert_eq!(*na, ne);
+        }
+
+        let n: Vec<Pos> = data.neighbours8((9, 9)).collect();
+        let exp = [(8, 8), (8, 9), (9, 8)];
+        assert_eq!(3, n.len());
+        for (na, ne) in n.iter().zip(exp) {
+            assert_eq!(*na, ne);
+        }
+
+        let n: Vec<Pos> = data.neighbours8((1, 1)).collect();
+        assert_eq!(8, n.len());
+        let exp = [
+            (0, 0),
+            (0, 1),
+            (0, 2),
+            (1, 0),
+            (1, 2),
+            (2, 0),
+            (2, 1),
+            (2, 2),
+        ];
+        for (na, ne) in n.iter().zip(exp) {
+            assert_eq!(*na, ne);
+        }
+    }
+}
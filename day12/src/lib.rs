@@ -0,0 +1,344 @@
+//! Day 12: Passage Pathing, counts the paths through the cave system
+
+use aoc_common::{Answer, Location, Result, Solver};
+use log::{debug, trace};
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::io::BufRead;
+
+aoc_common::parse_error! {
+    /// Errors found parsing the connections between caves
+    pub enum ParseError {
+        /// A line that is not two caves joined by '-'
+        InvalidConnection(Location),
+        /// A cave name that is empty or not made of letters
+        InvalidCave(Location),
+        /// There is no connection from the start cave
+        MissingStart,
+    }
+
+    fn fmt(&self, f) {
+        ParseError::InvalidConnection(loc) => {
+            write!(f, "{}: invalid connection '{}'", loc, loc.token)
+        }
+        ParseError::InvalidCave(loc) => write!(f, "{}: invalid cave '{}'", loc, loc.token),
+        ParseError::MissingStart => write!(f, "no connection from the start cave"),
+    }
+}
+
+type ParseResult<T> = std::result::Result<T, ParseError>;
+
+/// Caves connected to every cave, connections go both ways
+pub type Map = HashMap<String, Vec<String>>;
+/// Caves visited from start to end
+pub type Path = Vec<String>;
+
+
+/// Reads the connections between caves, there must be a start cave
+pub fn parse_input<I: BufRead>(reader: I) -> ParseResult<Map> {
+    let mut map = HashMap::new();
+
+    for (cnt, line_) in reader.lines().enumerate() {
+        let line = line_?;
+        let (first, second) = match line.trim().split_once('-') {
+            Some(caves) => caves,
+            None => {
+                let loc = Location::in_line(cnt + 1, &line, line.trim());
+                return Err(ParseError::InvalidConnection(loc));
+            }
+        };
+        for cave in [first, second] {
+            if cave.is_empty() || !cave.chars().all(|c| c.is_ascii_alphabetic()) {
+                return Err(ParseError::InvalidCave(Location::in_line(cnt + 1, &line, cave)));
+            }
+        }
+        let first = first.to_string();
+        let second = second.to_string();
+
+        match map.get_mut(&first) {
+            None => {
+                let _ = map.insert(String::from(&first), vec![String::from(&second)]);
+            },
+            Some(vec) => vec.push(String::from(&second)),
+        }
+
+        match map.get_mut(&second) {
+            None => {
+                let _ = map.insert(String::from(&second), vec![String::from(&first)]);
+            },
+            Some(vec) => vec.push(String::from(&first)),
+        }
+
+
+    }
+
+    if !map.contains_key("start") {
+        return Err(ParseError::MissingStart);
+    }
+    Ok(map)
+}
+
+/// Paths from start to end visiting the small caves at most once
+pub fn find_all_paths(map: &Map) -> Vec<Path> {
+    let mut paths: Vec<Path> = Vec::new();
+    let mut open_paths : VecDeque<Path> = VecDeque::new();
+
+
+    let start: &Vec<String> = map.get(&String::from("start")).unwrap();
+    start.iter().for_each(|n| {
+        open_paths.push_back(vec!["start".to_string(), n.to_string()]);
+    });
+
+    while !open_paths.is_empty() {
+        let current_path = open_paths.pop_front().unwrap();
+        let last_node = &current_path[current_path.len()-1];
+
+        if let Some(adjacent_nodes) = map.get(last_node) {
+            for adjacent_node in adjacent_nodes {
+
+                let is_big_node = adjacent_node.chars().next().unwrap().is_uppercase();
+                if is_big_node || !current_path.contains(adjacent_node) {
+
+                    let mut new_path = current_path.to_vec();
+                    new_path.push(adjacent_node.to_string());
+
+                    if "end" == adjacent_node {
+                        trace!("found path {:?}", new_path);
+                        paths.push(new_path);
+
+                    } else {
+                        open_paths.push_back(new_path);
+                    }
+                }
+            }
+        };
+
+
+    }
+
+    paths
+}
+
+
+fn path_contains_count(current_path: &Path, adjacent_node: &String) -> usize {
+    let mut counter = 0;
+    current_path.iter().for_each(|x| {
+        if x == adjacent_node {counter +=1;}
+    });
+    counter
+}
+
+/// Paths visiting `small_with_double` exactly twice and every other small cave at most
+/// once
+pub fn find_all_paths_with_one_double_entry(map: &Map, small_with_double: &String) -> Vec<Path> {
+    let mut paths: Vec<Path> = Vec::new();
+    let mut open_paths : VecDeque<Path> = VecDeque::new();
+
+
+    let start: &Vec<String> = map.get(&String::from("start")).unwrap();
+    start.iter().for_each(|n| {
+        open_paths.push_back(vec!["start".to_string(), n.to_string()]);
+    });
+
+
+    while !open_paths.is_empty() {
+        let current_path = open_paths.pop_front().unwrap();
+        let last_node = &current_path[current_path.len()-1];
+
+        if let Some(adjacent_nodes) = map.get(last_node) {
+            for adjacent_node in adjacent_nodes {
+
+                let is_big_node = adjacent_node.chars().next().unwrap().is_uppercase();
+                let continue_processing = is_big_node
+                    || !current_path.contains(adjacent_node)
+                    || ((adjacent_node == small_with_double) && (2 > path_contains_count(&current_path, adjacent_node)));
+
+                if continue_processing {
+
+                    let mut new_path = current_path.to_vec();
+                    new_path.push(adjacent_node.to_string());
+
+                    if "end" == adjacent_node {
+                        if 2 == path_contains_count(&current_path, small_with_double) {
+                            trace!("found path {:?}", new_path);
+                            paths.push(new_path);
+                        }
+
+                    } else {
+                        open_paths.push_back(new_path);
+                    }
+                }
+            }
+        };
+
+
+    }
+
+    paths
+}
+
+/// Small caves other than start and end
+pub fn get_list_of_small_nodes(map: &Map) -> Vec<String> {
+
+    let list_of_small_nodes: Vec::<String> = map.keys()
+        .filter(|x| *x != "start")
+        .filter(|x| *x != "end")
+        .filter(|x| x.chars().next().unwrap().is_lowercase())
+        .map(|x| x.to_string())
+        .collect();
+
+    list_of_small_nodes
+}
+
+/// Solver of the day, part 1 visits small caves once and part 2 allows a single small cave
+/// twice
+pub struct Day12;
+
+impl Solver for Day12 {
+    const DAY: u32 = 12;
+
+    type Input = Map;
+
+    fn parse<R: BufRead>(&self, reader: R) -> Result<Map> {
+        Ok(parse_input(reader)?)
+    }
+
+    fn part1(&self, map: &Map) -> Result<Answer> {
+        Ok(find_all_paths(map).len().into())
+    }
+
+    fn part2(&self, map: &Map) -> Result<Answer> {
+        let mut paths = find_all_paths(map);
+        for small_node in get_list_of_small_nodes(map) {
+            let mut double_paths = find_all_paths_with_one_double_entry(map, &small_node);
+            debug!("{} paths visiting {} twice", double_paths.len(), small_node);
+            paths.append(&mut double_paths);
+        }
+        Ok(paths.len().into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use std::io;
+
+    const F1: &[u8] = b"start-A
+start-b
+A-c
+A-b
+b-d
+A-end
+b-end" as &[u8];
+
+    const F2 : &[u8] = b"dc-end
+HN-start
+start-kj
+dc-start
+dc-HN
+LN-dc
+HN-end
+kj-sa
+kj-HN
+kj-dc" as &[u8];
+
+
+    #[test]
+    fn test_find_all_paths_with_one_double_entry() {
+        let reader = io::BufReader::new(F1);
+        let map = parse_input(reader).unwrap();
+
+        let list_of_small_nodes = get_list_of_small_nodes(&map);
+
+        let mut all_paths = find_all_paths(&map);
+
+        for small_node in list_of_small_nodes {
+            all_paths.append(&mut find_all_paths_with_one_double_entry(&map, &small_node) );
+        }
+        assert_eq!(36, all_paths.len());
+
+    }
+
+    #[test]
+    fn test_get_list_of_small_nodes() {
+        let reader = io::BufReader::new(F2);
+        let map = parse_input(reader).unwrap();
+
+        let list_of_small_nodes = get_list_of_small_nodes(&map);
+        let expected = ["kj", "dc", "sa"];
+
+        assert_eq!(expected.len(), list_of_small_nodes.len());
+
+
+    }
+
+    #[test]
+    fn test_find_all_paths() {
+        let reader = io::BufReader::new(F2);
+        let map = parse_input(reader).unwrap();
+        let all_paths = find_all_paths(&map);
+
+        assert_eq!(19, all_paths.len());
+    }
+
+
+    #[test]
+    fn test_find_all_paths_simple() {
+        let reader = io::BufReader::new(F1);
+        let map = parse_input(reader).unwrap();
+        let all_paths = find_all_paths(&map);
+
+        assert_eq!(10, all_paths.len());
+    }
+
+
+    #[test]
+    fn test_parse_input() {
+        let reader = io::BufReader::new(F2);
+        let map = parse_input(reader).unwrap();
+
+        let start = String::from("start");
+        assert!(map.contains_key(&start));
+        let vec = map.get(&start).unwrap();
+        assert_eq!(3, vec.len());
+    }
+
+
+    #[test]
+    fn test_parse_input_simple() {
+        let reader = io::BufReader::new(F1);
+        let map = parse_input(reader).unwrap();
+
+        let start = String::from("start");
+        assert!(map.contains_key(&start));
+
+
+        let a = String::from("A");
+        assert!(map.contains_key(&a));
+        let vec =  map.get(&a).unwrap();
+
+        assert_eq!(4, vec.len());
+        let connects = ["start", "c", "b", "end"];
+        for c in connects {
+            assert!( vec.contains( &c.to_string() ) );
+            }
+    }
+
+    #[test]
+    fn test_parse_input_errors() {
+        match parse_input(b"start-A\nA end" as &[u8]) {
+            Err(ParseError::InvalidConnection(loc)) => {
+                assert_eq!(Location::new(2, 1, "A end"), loc)
+            }
+            _ => panic!("expected an invalid connection"),
+        }
+
+        match parse_input(b"start-A\nA-3" as &[u8]) {
+            Err(ParseError::InvalidCave(loc)) => assert_eq!(Location::new(2, 3, "3"), loc),
+            _ => panic!("expected an invalid cave"),
+        }
+
+        assert!(matches!(parse_input(b"b-A\nA-end" as &[u8]), Err(ParseError::MissingStart)));
+    }
+}
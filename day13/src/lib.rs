@@ -0,0 +1,312 @@
+//! Day 13: Transparent Origami, folds the transparent paper to read the activation code
+
+use aoc_common::{Answer, Error, Location, Result, Solver};
+use log::debug;
+use std::collections::HashSet;
+use std::io::BufRead;
+
+aoc_common::parse_error! {
+    /// Errors found parsing the points and folds of the paper
+    pub enum ParseError {
+        /// A dot coordinate that is not a positive integer
+        InvalidPoint(Location),
+        /// A line that is not like "fold along x=5"
+        InvalidFold(Location),
+    }
+
+    fn fmt(&self, f) {
+        ParseError::InvalidPoint(loc) => {
+            write!(f, "{}: invalid coordinate '{}'", loc, loc.token)
+        }
+        ParseError::InvalidFold(loc) => write!(f, "{}: invalid fold '{}'", loc, loc.token),
+    }
+}
+
+type ParseResult<T> = std::result::Result<T, ParseError>;
+
+/// Direction of the fold line
+#[derive(Debug, Clone, PartialEq)]
+pub enum Axis {
+    /// Along y, folds the bottom half up
+    Horizontal,
+    /// Along x, folds the right half left
+    Vertical,
+}
+
+/// Fold along `line` in the direction of `axis`
+#[derive(Debug, Clone)]
+pub struct Instruction {
+    line: usize,
+    axis: Axis,
+}
+
+impl Instruction {
+    pub fn new(line: usize, axis: Axis) -> Instruction {
+        Instruction{line, axis}
+    }
+}
+
+/// Dots of the paper as (x, y)
+pub type Points = HashSet<(usize, usize)>;
+
+/// Parses a fold like "fold along y=7", line_no starts at 1
+fn parse_fold(line_no: usize, line: &str) -> ParseResult<Instruction> {
+    let invalid = || ParseError::InvalidFold(Location::in_line(line_no, line, line.trim()));
+
+    let (axis, val) = line
+        .trim()
+        .strip_prefix("fold along ")
+        .and_then(|fold| fold.split_once('='))
+        .ok_or_else(invalid)?;
+    let axis = match axis {
+        "x" => Axis::Vertical,
+        "y" => Axis::Horizontal,
+        _ => return Err(invalid()),
+    };
+    match val.parse::<usize>() {
+        Ok(val) => Ok(Instruction::new(val, axis)),
+        Err(_) => Err(ParseError::InvalidFold(Location::in_line(line_no, line, val))),
+    }
+}
+
+/// Reads the dots and the fold instructions that follow them
+pub fn parse_input<I: BufRead>(reader: I) -> ParseResult<(Points, Vec<Instruction>)> {
+    let mut points = Points::new();
+    let mut instructions: Vec<Instruction> = vec![];
+
+    for (cnt, line_) in reader.lines().enumerate() {
+        let line = line_?;
+        if line.contains(",") {
+            let mut coordinates = [0; 2];
+            for (coordinate, part) in coordinates.iter_mut().zip(line.splitn(2, ',')) {
+                let part = part.trim();
+                *coordinate = match part.parse::<usize>() {
+                    Ok(val) => val,
+                    Err(_) => {
+                        let loc = Location::in_line(cnt + 1, &line, part);
+                        return Err(ParseError::InvalidPoint(loc));
+                    }
+                };
+            }
+            points.insert((coordinates[0], coordinates[1]));
+        } else if !line.trim().is_empty() {
+            instructions.push(parse_fold(cnt + 1, &line)?);
+        }
+    }
+
+    Ok((points, instructions))
+}
+
+fn find_max_coordinates(points: &Points) -> (usize, usize) {
+    let mut max_i = 0;
+    let mut max_j = 0;
+
+    for (i, j) in points {
+        if *i > max_i { max_i = *i };
+        if *j > max_j { max_j = *j };
+    }
+    (max_i, max_j)
+}
+
+fn fold_up(points: &mut Points, fold_pos: usize)  {
+
+    let drained: Vec<(usize, usize)> = points
+        .extract_if(|(_i, j)| *j > fold_pos)
+        .collect();
+
+    for (i, j) in drained {
+        points.insert((i, 2*fold_pos - j ));
+    }
+    debug!("fold along y={}: {} points left", fold_pos, points.len());
+}
+
+fn fold_left(points: &mut Points, fold_pos: usize)  {
+let drained: Vec<(usize, usize)> = points
+        .extract_if(|(i, _j)| *i > fold_pos)
+        .collect();
+
+    for (i, j) in drained {
+        points.insert((2*fold_pos - i, j ));
+    }
+    debug!("fold along x={}: {} points left", fold_pos, points.len());
+}
+
+/// Folds the paper, overlapping dots merge
+pub fn execute_instruction(points: &mut Points, instruction: &Instruction) {
+    if instruction.axis == Axis::Horizontal {
+        fold_up(points, instruction.line);
+    } else {
+        fold_left(points, instruction.line);
+    }
+}
+
+/// Draws the points as lines of text, one line per row
+pub fn render(points: &Points) -> String {
+    let (max_i, max_j) = find_max_coordinates(points);
+    let mut text: Vec<Vec<char>>= Vec::with_capacity(max_j);
+    for _ in 0..=max_j {
+        text.push(vec![' ';max_i+1]);
+    }
+    for (i, j) in points.iter() {
+        text[*j][*i] = '#';
+    }
+
+    text.into_iter()
+        .map(|line| line.into_iter().collect::<String>().trim_end().to_string())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Solver of the day, part 1 counts the dots after the first fold and part 2 draws the code
+pub struct Day13;
+
+impl Solver for Day13 {
+    const DAY: u32 = 13;
+
+    type Input = (Points, Vec<Instruction>);
+
+    fn parse<R: BufRead>(&self, reader: R) -> Result<(Points, Vec<Instruction>)> {
+        Ok(parse_input(reader)?)
+    }
+
+    fn part1(&self, (points, folds): &(Points, Vec<Instruction>)) -> Result<Answer> {
+        let mut points = points.clone();
+        match folds.first() {
+            Some(fold) => execute_instruction(&mut points, fold),
+            None => return Err(Error::InvalidInput(String::from("no fold instructions"))),
+        }
+        Ok(points.len().into())
+    }
+
+    fn part2(&self, (points, folds): &(Points, Vec<Instruction>)) -> Result<Answer> {
+        let mut points = points.clone();
+        for fold in folds {
+            execute_instruction(&mut points, fold)
+        }
+        Ok(render(&points).into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io;
+
+    const F: &[u8] = b"6,10
+0,14
+9,10
+0,3
+10,4
+4,11
+6,0
+6,12
+4,1
+0,13
+10,12
+3,4
+3,0
+8,4
+1,10
+2,14
+8,10
+9,0
+
+fold along y=7
+fold along x=5" as &[u8];
+
+
+
+
+    #[test]
+    fn test_execute_instruction() {
+
+        let reader = io::BufReader::new(F);
+        let (mut points, folds) = parse_input(reader).unwrap();
+
+        assert_eq!(18, points.len());
+
+        let mut instruction_iter = folds.iter();
+
+        execute_instruction(&mut points, instruction_iter.next().unwrap());
+        assert_eq!(17, points.len());
+
+        execute_instruction(&mut points, instruction_iter.next().unwrap());
+        assert_eq!(16, points.len());
+
+    }
+
+
+    #[test]
+    fn test_fold_up_left() {
+
+        let reader = io::BufReader::new(F);
+        let (mut points, _folds) = parse_input(reader).unwrap();
+
+        assert_eq!(18, points.len());
+
+        fold_up(&mut points, 7);
+        assert_eq!(17, points.len());
+
+        fold_left(&mut points, 5);
+        assert_eq!(16, points.len());
+
+    }
+
+    #[test]
+    fn test_find_max_coordinates() {
+        let reader = io::BufReader::new(F);
+        let (points, _folds) = parse_input(reader).unwrap();
+
+        let (mx, my) = find_max_coordinates(&points);
+        assert_eq!(10, mx);
+        assert_eq!(14, my);
+    }
+
+    #[test]
+    fn test_parse_input() {
+        let reader = io::BufReader::new(F);
+        let (points, folds) = parse_input(reader).unwrap();
+
+        assert_eq!(18, points.len());
+        assert_eq!(2, folds.len());
+
+        let epoints: [(usize, usize); 18] = [(6,10), (0,14), (9,10), (0,3), (10,4), (4,11), (6,0),
+                       (6,12), (4,1), (0,13), (10,12), (3,4), (3,0), (8,4), (1,10),
+                       (2,14), (8,10), (9,0)];
+
+        for ep in epoints {
+            assert_eq!(Some(&ep), points.get(&ep));
+        }
+
+        let mut fold_iter = folds.iter();
+
+        let fold = fold_iter.next().unwrap();
+        assert_eq!(Axis::Horizontal, fold.axis);
+        assert_eq!(7, fold.line);
+
+        let fold = fold_iter.next().unwrap();
+        assert_eq!(Axis::Vertical, fold.axis);
+        assert_eq!(5, fold.line);
+
+    }
+
+    #[test]
+    fn test_parse_input_errors() {
+        match parse_input(b"6,10\n0,-4" as &[u8]) {
+            Err(ParseError::InvalidPoint(loc)) => assert_eq!(Location::new(2, 3, "-4"), loc),
+            _ => panic!("expected an invalid point"),
+        }
+
+        match parse_input(b"6,10\n\nfold along z=7" as &[u8]) {
+            Err(ParseError::InvalidFold(loc)) => {
+                assert_eq!(Location::new(3, 1, "fold along z=7"), loc)
+            }
+            _ => panic!("expected an invalid fold"),
+        }
+
+        match parse_input(b"6,10\n\nfold along y=7\nfold along x=a" as &[u8]) {
+            Err(ParseError::InvalidFold(loc)) => assert_eq!(Location::new(4, 14, "a"), loc),
+            _ => panic!("expected an invalid fold"),
+        }
+    }
+}
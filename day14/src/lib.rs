@@ -0,0 +1,422 @@
+//! Day 14: Extended Polymerization, grows the polymer counting pairs instead of building it
+
+use aoc_common::count::add_to;
+use aoc_common::{Answer, Count, Error, Location, Result, Solver};
+use log::{debug, trace};
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
+use std::io::BufRead;
+
+/// Number of times every pair of elements appears in the polymer
+pub type Buckets<C = usize> = HashMap<String, C>;
+/// Element inserted between every pair
+pub type Map = HashMap<String, char>;
+/// Number of times every element appears in the polymer
+pub type ElementMap<C = usize> = HashMap<char, C>;
+
+aoc_common::parse_error! {
+    /// Errors found parsing the polymer template and the insertion rules
+    pub enum ParseError {
+        /// A polymer template with characters that are not elements
+        InvalidTemplate(Location),
+        /// An insertion rule that is not like "CH -> B"
+        InvalidRule(Location),
+        /// A pair with no insertion rule, located at the template or the rule producing it
+        MissingRule(String, Location),
+    }
+
+    fn fmt(&self, f) {
+        ParseError::InvalidTemplate(loc) => {
+            write!(f, "{}: invalid polymer template '{}'", loc, loc.token)
+        }
+        ParseError::InvalidRule(loc) => {
+            write!(f, "{}: invalid insertion rule '{}'", loc, loc.token)
+        }
+        ParseError::MissingRule(pair, loc) => write!(
+            f,
+            "{}: no insertion rule for the pair '{}' of '{}'",
+            loc, pair, loc.token
+        ),
+    }
+}
+
+type ParseResult<T> = std::result::Result<T, ParseError>;
+
+/// Parses an insertion rule like "CH -> B", line_no starts at 1
+fn parse_rule(line_no: usize, line: &str) -> ParseResult<(String, char)> {
+    let rule = line.trim();
+    if let Some((key, c)) = rule.split_once("->") {
+        let (key, c) = (key.trim(), c.trim());
+        let elements =
+            |s: &str, n: usize| s.len() == n && s.chars().all(|c| c.is_ascii_uppercase());
+        if elements(key, 2) && elements(c, 1) {
+            return Ok((key.to_string(), c.chars().next().unwrap()));
+        }
+    }
+    Err(ParseError::InvalidRule(Location::in_line(line_no, line, rule)))
+}
+
+/// Checks that every pair the polymer can grow has an insertion rule, `pairs` are the
+/// pairs of the template and `rules` where every rule is
+fn check_rules(
+    pairs: Vec<(String, Location)>,
+    map: &Map,
+    rules: &HashMap<String, Location>,
+) -> ParseResult<()> {
+    let mut seen = HashSet::new();
+    let mut pending = pairs;
+    while let Some((pair, loc)) = pending.pop() {
+        if !seen.insert(pair.clone()) {
+            continue;
+        }
+        let c = match map.get(&pair) {
+            Some(c) => *c,
+            None => return Err(ParseError::MissingRule(pair, loc)),
+        };
+        let mut chars = pair.chars();
+        let (first, second) = (chars.next().unwrap_or(c), chars.next().unwrap_or(c));
+        for new_pair in [format!("{}{}", first, c), format!("{}{}", c, second)] {
+            pending.push((new_pair, rules[&pair].clone()));
+        }
+    }
+    Ok(())
+}
+
+/// Reads the template as pair and element counts and the insertion rules
+///
+/// Fails too if a pair the polymer grows has no insertion rule.
+pub fn parse_input<I: BufRead>(reader: I) -> ParseResult<(Buckets, Map, ElementMap)> {
+    let mut buckets = Buckets::new();
+    let mut map = Map::new();
+    let mut elements = ElementMap::new();
+    let mut pairs = vec![];
+    let mut rules = HashMap::new();
+
+    /*
+    let alphabet: Vec<char> = "ABCDEFGHIJKLMNOPQRSTUVWXYZ".chars().collect();
+    for a in &alphabet {
+        for b in &alphabet {
+            buckets.insert(format!("{}{}", a, b), 0);
+        }
+    }
+     */
+    for (cnt, line_) in reader.lines().enumerate() {
+        let line =  line_?;
+
+        if line.contains("->") {
+            let (key, c) = parse_rule(cnt + 1, &line)?;
+            rules.insert(key.clone(), Location::in_line(cnt + 1, &line, line.trim()));
+            map.insert(key, c);
+        } else {
+            if !line.is_empty() {
+                if !line.chars().all(|c| c.is_ascii_uppercase()) {
+                    let loc = Location::in_line(cnt + 1, &line, &line);
+                    return Err(ParseError::InvalidTemplate(loc));
+                }
+                let vec: Vec<char> = line.chars().collect();
+                vec.iter().for_each(|c| *elements.entry(*c).or_insert(0) += 1);
+
+                for (i, pair) in vec.windows(2).enumerate() {
+                    let key = format!("{}{}", pair[0], pair[1]);
+                    pairs.push((key.clone(), Location::in_line(cnt + 1, &line, &line[i..i + 2])));
+                    *buckets.entry(key).or_insert(0) += 1;
+                }
+            }
+        }
+    }
+    check_rules(pairs, &map, &rules)?;
+
+    Ok((buckets, map, elements))
+}
+
+/// Applies the insertion rules once to every pair, fails if a count overflows `C` or a pair
+/// has no insertion rule
+pub fn step<C: Count>(
+    buckets: &mut Buckets<C>,
+    map: &Map,
+    elements: &mut ElementMap<C>,
+) -> Result<()> {
+    let pairs = buckets.clone();
+
+    for (key, val) in pairs.iter() {
+
+        let new_char = map
+            .get(key)
+            .ok_or_else(|| Error::InvalidInput(format!("no insertion rule for '{}'", key)))?;
+        add_to(elements.entry(*new_char).or_insert_with(C::zero), val, "number of elements")?;
+
+        let mut key_iter = key.chars();
+        let pair_first_char = key_iter.next().unwrap();
+        let pair_second_char = key_iter.next().unwrap();
+
+        let new_pair_1 = format!("{}{}",
+                               pair_first_char,
+                               new_char);
+
+        let new_pair_2 = format!("{}{}",
+                                 new_char,
+                                 pair_second_char);
+
+
+        if let Entry::Occupied(mut o) = buckets.entry(String::from(key)) {
+            match o.get().try_sub(val) {
+                Some(left) if left > C::zero() => *o.get_mut() = left,
+                _ => {
+                    o.remove_entry();
+                }
+            }
+        }
+
+        add_to(buckets.entry(new_pair_1).or_insert_with(C::zero), val, "number of pairs")?;
+        add_to(buckets.entry(new_pair_2).or_insert_with(C::zero), val, "number of pairs")?;
+    }
+    trace!("pairs {:?}", buckets);
+    trace!("elements {:?}", elements);
+    Ok(())
+}
+
+/// Difference between the most and least common elements
+pub fn get_difference<C: Count>(elements: &ElementMap<C>) -> Result<C> {
+    let most = elements.values().max();
+    let least = elements.values().min();
+    match (most, least) {
+        (Some(most), Some(least)) => Ok(most.try_sub(least).expect("most is not less than least")),
+        _ => Err(Error::InvalidInput(String::from("empty polymer template"))),
+    }
+}
+
+/// Difference between the most and least common elements after the given steps, counted
+/// with `C`
+///
+/// `usize` overflows after about 60 steps, `difference_after::<BigUint>` can take any
+/// number of steps with the `bigint` feature.
+pub fn difference_after<C: Count>(input: &(Buckets, Map, ElementMap), steps: usize) -> Result<C> {
+    let (buckets, map, elements) = input;
+    let mut buckets: Buckets<C> =
+        buckets.iter().map(|(k, v)| (k.clone(), C::from_u64(*v as u64))).collect();
+    let mut elements: ElementMap<C> =
+        elements.iter().map(|(k, v)| (*k, C::from_u64(*v as u64))).collect();
+    for n in 0..steps {
+        step(&mut buckets, map, &mut elements)?;
+        debug!("step {}: {} distinct pairs", n + 1, buckets.len());
+    }
+    get_difference(&elements)
+}
+
+/// Solver of the day, part 1 runs 10 steps and part 2 runs 40
+pub struct Day14;
+
+impl Solver for Day14 {
+    const DAY: u32 = 14;
+
+    type Input = (Buckets, Map, ElementMap);
+
+    fn parse<R: BufRead>(&self, reader: R) -> Result<(Buckets, Map, ElementMap)> {
+        Ok(parse_input(reader)?)
+    }
+
+    fn part1(&self, input: &(Buckets, Map, ElementMap)) -> Result<Answer> {
+        Ok(difference_after::<usize>(input, 10)?.into())
+    }
+
+    fn part2(&self, input: &(Buckets, Map, ElementMap)) -> Result<Answer> {
+        Ok(difference_after::<usize>(input, 40)?.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::io;
+
+    const F: &[u8] = b"NNCB
+
+CH -> B
+HH -> N
+CB -> H
+NH -> C
+HB -> C
+HC -> B
+HN -> C
+NN -> C
+BH -> H
+NC -> B
+NB -> B
+BN -> B
+BB -> N
+BC -> B
+CC -> N
+CN -> C" as &[u8];
+
+    fn get_elements_sum(elements: &ElementMap ) -> usize {
+        let mut sum = 0;
+        for val in  elements.values() {
+            sum += *val;
+        }
+        sum
+    }
+    fn get_buckets_sum(b: &Buckets ) -> usize {
+        let mut sum = 0;
+        for val in b.values() {
+            sum += *val;
+        }
+        sum
+    }
+
+    #[test]
+    fn test_get_difference() {
+        let reader = io::BufReader::new(F);
+        let (mut buckets, map, mut elements) = parse_input(reader).unwrap();
+        for _ in 0..10 {
+            step(&mut buckets, &map, &mut elements).unwrap();
+        }
+
+        assert_eq!(1588, get_difference(&elements).unwrap());
+    }
+
+    #[test]
+    fn test_step() {
+        let reader = io::BufReader::new(F);
+        let (mut buckets, map, mut elements) = parse_input(reader).unwrap();
+
+        let mut elements_sum = get_elements_sum(&elements);
+        let buckets_sum = get_buckets_sum(&buckets);
+        assert_eq!(4, elements_sum);
+        assert_eq!(elements_sum-1, buckets_sum);
+
+        step(&mut buckets, &map, &mut elements).unwrap();
+
+        elements_sum = 2*elements_sum - 1;
+        assert_eq!(elements_sum, get_elements_sum(&elements));
+        assert_eq!(elements_sum-1, get_buckets_sum(&buckets));
+
+        assert_eq!(Some(&2), elements.get(&'B'));
+        assert_eq!(Some(&2), elements.get(&'N'));
+        assert_eq!(Some(&2), elements.get(&'C'));
+        assert_eq!(Some(&1), elements.get(&'H'));
+
+        assert_eq!( Some(&(1_usize)), buckets.get(&String::from("NC")));
+        assert_eq!( Some(&(1_usize)), buckets.get(&String::from("CN")));
+        assert_eq!( Some(&(1_usize)), buckets.get(&String::from("NB")));
+        assert_eq!( Some(&(1_usize)), buckets.get(&String::from("BC")));
+        assert_eq!( Some(&(1_usize)), buckets.get(&String::from("CH")));
+        assert_eq!( Some(&(1_usize)), buckets.get(&String::from("HB")));
+
+
+        step(&mut buckets, &map, &mut elements).unwrap();
+
+        elements_sum = 2*elements_sum - 1;
+        assert_eq!(elements_sum, get_elements_sum(&elements));
+        assert_eq!(elements_sum-1, get_buckets_sum(&buckets));
+
+        assert_eq!(Some(&6), elements.get(&'B'));
+        assert_eq!(Some(&2), elements.get(&'N'));
+        assert_eq!(Some(&4), elements.get(&'C'));
+        assert_eq!(Some(&1), elements.get(&'H'));
+
+        step(&mut buckets, &map, &mut elements).unwrap();
+
+        elements_sum = 2*elements_sum - 1;
+        assert_eq!(elements_sum, get_elements_sum(&elements));
+        assert_eq!(elements_sum-1, get_buckets_sum(&buckets));
+
+        assert_eq!(Some(&11), elements.get(&'B'));
+
+
+        for _ in 3..10 {
+            step(&mut buckets, &map, &mut elements).unwrap();
+            elements_sum = 2*elements_sum - 1;
+            assert_eq!(elements_sum, get_elements_sum(&elements));
+            assert_eq!(elements_sum-1, get_buckets_sum(&buckets));
+        }
+        assert_eq!(Some(&1749), elements.get(&'B'));
+        assert_eq!(Some(&298), elements.get(&'C'));
+        assert_eq!(Some(&161), elements.get(&'H'));
+        assert_eq!(Some(&865), elements.get(&'N'));
+
+
+        assert_eq!(16, map.len());
+    }
+
+    #[test]
+    fn test_parse_input() {
+        let reader = io::BufReader::new(F);
+        let (buckets, map, elements) = parse_input(reader).unwrap();
+
+        assert_eq!(3, elements.len());
+        assert_eq!(Some(&2), elements.get(&'N'));
+
+
+        assert_eq!(3, buckets.len());
+
+        let pairs = ["NN", "NC", "CB"];
+
+        for pair in pairs {
+            assert_eq!( Some(&(1_usize)), buckets.get(pair));
+        }
+
+        assert_eq!(16, map.len());
+
+        let pairs = ["CH", "HH", "CB", "NH",       "CN"];
+        let chars = ['B',  'N',  'H',  'C',        'C' ];
+
+        for (p, c) in pairs.iter().zip(chars) {
+            assert_eq!(Some(&c), map.get(*p));
+        }
+    }
+
+    #[test]
+    fn test_parse_input_errors() {
+        match parse_input(b"NNCB\n\nCH -> B\nHH - N" as &[u8]) {
+            Err(ParseError::InvalidTemplate(loc)) => assert_eq!(Location::new(4, 1, "HH - N"), loc),
+            _ => panic!("expected an invalid template"),
+        }
+
+        match parse_input(b"NNCB\n\nCH -> B\n HHH -> N" as &[u8]) {
+            Err(ParseError::InvalidRule(loc)) => assert_eq!(Location::new(4, 2, "HHH -> N"), loc),
+            _ => panic!("expected an invalid rule"),
+        }
+
+        match parse_input(b"NCN\n\nNC -> N\nNN -> N" as &[u8]) {
+            Err(ParseError::MissingRule(pair, loc)) => {
+                assert_eq!(("CN", Location::new(1, 2, "CN")), (pair.as_str(), loc))
+            }
+            _ => panic!("expected a missing rule"),
+        }
+
+        // pairs grown by the rules need a rule too
+        match parse_input(b"NN\n\nNN -> C" as &[u8]) {
+            Err(ParseError::MissingRule(_, loc)) => assert_eq!(Location::new(3, 1, "NN -> C"), loc),
+            _ => panic!("expected a missing rule"),
+        }
+        assert!(parse_input(b"NN\n\nNN -> N" as &[u8]).is_ok());
+
+        let mut buckets = Buckets::from([(String::from("NN"), 1_usize)]);
+        let mut elements = ElementMap::from([('N', 2_usize)]);
+        assert!(step(&mut buckets, &Map::new(), &mut elements).is_err());
+    }
+
+    #[test]
+    fn test_difference_after() {
+        let input = parse_input(F).unwrap();
+        assert_eq!(2188189693529, difference_after::<usize>(&input, 40).unwrap());
+
+        match difference_after::<usize>(&input, 100) {
+            Err(Error::Overflow(_)) => (),
+            _ => panic!("expected an overflow"),
+        }
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn test_difference_after_bigint() {
+        use aoc_common::BigUint;
+
+        let input = parse_input(F).unwrap();
+        let difference = difference_after::<BigUint>(&input, 40).unwrap();
+        assert_eq!(BigUint::from(2188189693529_u64), difference);
+
+        let difference = difference_after::<BigUint>(&input, 200).unwrap();
+        assert!(difference > BigUint::from(u64::MAX));
+    }
+}
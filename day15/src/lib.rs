@@ -0,0 +1,206 @@
+//! Day 15: Chiton, finds the path through the cave with the lowest total risk
+
+use aoc_common::{Answer, Error, Grid, Pos, Result, Solver};
+use log::debug;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::io::BufRead;
+
+/// Risk level of every position of the cave
+pub type Map = Grid<u32>;
+
+/// Returns the risk level of every position of the map
+pub fn parse_input<I: BufRead>(reader: I) -> Result<Map> {
+    Ok(Grid::parse_digits(reader)?)
+}
+
+/// Lowest total risk from the top left to the bottom right, the start is not counted
+pub fn get_shortest_path_value(map: &Map) -> u32 {
+    let mut unvisited_nodes = Grid::from_elem(map.rows(), map.cols(), u32::MAX);
+    let first_idx = (0, 0);
+    unvisited_nodes[first_idx] = 0;
+
+    let mut visited_nodes: BinaryHeap<(Reverse<u32>, Pos)> = BinaryHeap::with_capacity(map.len());
+    visited_nodes.push((Reverse(0), first_idx));
+
+    let mut max_frontier = visited_nodes.len();
+    let mut popped = 0;
+    while let Some((Reverse(current_node_cost), idx)) = visited_nodes.pop() {
+        popped += 1;
+        for nidx in map.neighbours4(idx) {
+            let new_neighbour_cost = current_node_cost + map[nidx];
+            let tmp_neighbour_cost = &mut unvisited_nodes[nidx];
+            if new_neighbour_cost < *tmp_neighbour_cost {
+                *tmp_neighbour_cost = new_neighbour_cost;
+
+                visited_nodes.push((Reverse(new_neighbour_cost), nidx));
+            }
+        }
+        max_frontier = max_frontier.max(visited_nodes.len());
+    }
+    debug!(
+        "{}x{} map: {} nodes popped, largest frontier {}",
+        map.rows(),
+        map.cols(),
+        popped,
+        max_frontier
+    );
+
+    unvisited_nodes[map.last_pos().unwrap()]
+}
+
+fn tile_inc(i: u32, j: u32) -> u32 {
+    let res = i + j;
+    if res <= 9 {
+        res
+    } else {
+        res % 9
+    }
+}
+
+/// Repeats the map 5 times in each direction increasing the risk of every tile
+pub fn tile_map(map: &Map) -> Map {
+    let (rows, cols) = (map.rows(), map.cols());
+    Grid::from_fn(5 * rows, 5 * cols, |(i, j)| {
+        tile_inc(map[(i % rows, j % cols)], (i / rows + j / cols) as u32)
+    })
+}
+
+/// Solver of the day, part 1 crosses the map and part 2 the map tiled 5 times
+pub struct Day15;
+
+impl Solver for Day15 {
+    const DAY: u32 = 15;
+
+    type Input = Map;
+
+    fn parse<R: BufRead>(&self, reader: R) -> Result<Map> {
+        let map = parse_input(reader)?;
+        if map.is_empty() {
+            return Err(Error::InvalidInput(String::from("empty map")));
+        }
+        Ok(map)
+    }
+
+    fn part1(&self, map: &Map) -> Result<Answer> {
+        Ok(get_shortest_path_value(map).into())
+    }
+
+    fn part2(&self, map: &Map) -> Result<Answer> {
+        Ok(get_shortest_path_value(&tile_map(map)).into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const F: &str = "1163751742
+1381373672
+2136511328
+3694931569
+7463417111
+1319128137
+1359912421
+3125421639
+1293138521
+2311944581";
+
+    #[test]
+    fn test_tile_map() {
+        let map = parse_input(F.as_bytes()).unwrap();
+        let new_map = tile_map(&map);
+
+        assert_eq!(new_map.rows(), 5 * map.rows());
+        assert_eq!(new_map.cols(), 5 * map.cols());
+        assert_eq!(map.row(0), &new_map.row(0)[..map.cols()]);
+        assert_eq!(2, new_map[(0, map.cols())]);
+        assert_eq!(3, new_map[(map.rows(), map.cols())]);
+
+        let sp = get_shortest_path_value(&new_map);
+        assert_eq!(315, sp);
+    }
+
+    #[test]
+    fn test_tile_inc() {
+        let is = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let es = [2, 3, 4, 5, 6, 7, 8, 9, 1];
+
+        for (i, e) in is.iter().zip(es) {
+            assert_eq!(e, tile_inc(*i, 1));
+        }
+
+        let es = [3, 4, 5, 6, 7, 8, 9, 1, 2];
+
+        for (i, e) in is.iter().zip(es) {
+            assert_eq!(e, tile_inc(*i, 2));
+        }
+    }
+
+    #[test]
+    fn test_get_shortest_path_value() {
+        let map = parse_input(F.as_bytes()).unwrap();
+
+        let sp = get_shortest_path_value(&map);
+        assert_eq!(40, sp);
+    }
+
+    #[test]
+    fn test_get_node_with_less_cost() {
+        let max_i = 10_usize;
+        let max_j = 10_usize;
+
+        let mut bheap = BinaryHeap::with_capacity((max_i + 1) * (max_j + 1));
+        let idx = [0, 0];
+        bheap.push((Reverse(5), idx));
+        bheap.push((Reverse(8), idx));
+        bheap.push((Reverse(2), idx));
+        bheap.push((Reverse(9), idx));
+
+        assert_eq!(Some((Reverse(2), idx)), bheap.pop());
+        assert_eq!(Some((Reverse(5), idx)), bheap.pop());
+        {
+            let mut p = match bheap.peek_mut() {
+                Some(it) => it,
+                _ => unreachable!(),
+            };
+            *p = (Reverse(15), idx);
+        }
+
+        //assert_eq!(8, *modify);
+        assert_eq!(Some((Reverse(9), idx)), bheap.pop());
+        assert_eq!(Some((Reverse(15), idx)), bheap.pop());
+        assert_eq!(None, bheap.pop());
+    }
+
+    #[test]
+    fn test_get_neighbours() {
+        let map = Map::from_elem(4, 4, 0);
+
+        let n: Vec<Pos> = map.neighbours4((0, 0)).collect();
+        assert_eq!(2, n.len());
+        assert!(n.contains(&(0, 1)));
+        assert!(n.contains(&(1, 0)));
+
+        let n: Vec<Pos> = map.neighbours4((3, 3)).collect();
+        assert_eq!(2, n.len());
+        assert!(n.contains(&(2, 3)));
+        assert!(n.contains(&(3, 2)));
+
+        let n: Vec<Pos> = map.neighbours4((1, 1)).collect();
+        assert_eq!(4, n.len());
+        assert!(n.contains(&(0, 1)));
+        assert!(n.contains(&(1, 0)));
+        assert!(n.contains(&(1, 2)));
+        assert!(n.contains(&(2, 1)));
+    }
+
+    #[test]
+    fn test_parse_input() {
+        //let reader = io::BufReader::new(F);
+        //let reader = include_str!(input_file);
+        let map = parse_input(F.as_bytes()).unwrap();
+
+        assert_eq!(100, map.len());
+    }
+}
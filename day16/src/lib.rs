@@ -0,0 +1,766 @@
+//! Day 16: Packet Decoder, decodes the packets of the BITS transmission
+
+use aoc_common::count::{add_to, overflow};
+use aoc_common::{Answer, Count, Location, Result, Solver};
+use bitstream_io::{BigEndian, BitRead, BitReader};
+use std::io::{self, BufRead, Cursor};
+
+aoc_common::parse_error! {
+    /// Errors found parsing the hexadecimal transmission
+    pub enum ParseError {
+        /// A character that is not an hexadecimal digit
+        InvalidHexDigit(Location),
+        /// The transmission does not have a whole number of bytes, points to the last digit
+        OddLength(Location),
+        /// The transmission ends in the middle of the field starting at this bit
+        Truncated(u64),
+        /// A literal starting at this bit that doesn't fit in 64 bits
+        LiteralTooLarge(u64),
+        /// The sub-packets of the operator starting at this bit overrun its length in bits
+        LengthMismatch(u64),
+        /// An operator with a number of sub-packets its type can't take
+        Operands {
+            bit: u64,
+            type_id: u64,
+            count: usize,
+        },
+        /// An operator starting at this bit nested in more than `MAX_NESTING` others
+        TooDeep(u64),
+    }
+
+    fn fmt(&self, f) {
+        ParseError::InvalidHexDigit(loc) => {
+            write!(f, "{}: invalid hexadecimal digit '{}'", loc, loc.token)
+        }
+        ParseError::OddLength(loc) => write!(f, "{}: odd number of hexadecimal digits", loc),
+        ParseError::Truncated(bit) => write!(f, "transmission truncated at bit {}", bit),
+        ParseError::LiteralTooLarge(bit) => {
+            write!(f, "bit {}: literal does not fit in 64 bits", bit)
+        }
+        ParseError::LengthMismatch(bit) => {
+            write!(
+                f,
+                "bit {}: sub-packets overrun the length of the packet",
+                bit
+            )
+        }
+        ParseError::Operands {
+            bit,
+            type_id,
+            count,
+        } => {
+            let expected = match type_id {
+                5..=7 => "exactly 2",
+                _ => "at least 1",
+            };
+            write!(
+                f,
+                "bit {}: operator of type {} has {} sub-packets, expected {}",
+                bit, type_id, count, expected
+            )
+        }
+        ParseError::TooDeep(bit) => write!(f, "bit {}: operator nested too deeply", bit),
+    }
+}
+
+type ParseResult<T> = std::result::Result<T, ParseError>;
+
+/// Converts the hexadecimal transmission to bytes, it must have an even number of digits
+pub fn parse_input(input: &str) -> ParseResult<Vec<u8>> {
+    let text = input.trim();
+    let mut nibble_stream = Vec::with_capacity(text.len());
+    for (i, c) in text.char_indices() {
+        match c.to_digit(16) {
+            Some(nibble) => nibble_stream.push(nibble as u8),
+            None => {
+                let loc = Location::in_line(1, text, &text[i..i + c.len_utf8()]);
+                return Err(ParseError::InvalidHexDigit(loc));
+            }
+        }
+    }
+
+    if nibble_stream.len() % 2 != 0 {
+        let loc = Location::in_line(1, text, &text[text.len() - 1..]);
+        return Err(ParseError::OddLength(loc));
+    }
+
+    Ok(nibble_stream
+        .chunks(2)
+        .map(|w| w[1] + 16 * w[0])
+        .collect::<Vec<u8>>())
+}
+
+/// Packet of the transmission with its header and contents
+#[derive(Debug)]
+pub struct Packet {
+    version: u64,
+    type_id: u64,
+    size: u64,
+    payload: Payload,
+}
+
+impl Packet {
+    /// Length of the packet and its sub-packets in bits
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    /// Version of the packet
+    pub fn version(&self) -> u64 {
+        self.version
+    }
+
+    /// Type of the packet, 4 for literals and the operation for the rest
+    pub fn type_id(&self) -> u64 {
+        self.type_id
+    }
+
+    pub fn payload(&self) -> &Payload {
+        &self.payload
+    }
+
+    /// Sum of the versions of the packet and all its sub-packets, fails if it doesn't fit
+    pub fn get_version_sum(&self) -> Result<u64> {
+        let mut ver_sum = self.version;
+
+        if let Payload::PacketList(pl) = &self.payload {
+            for sp in pl {
+                add_to(&mut ver_sum, &sp.get_version_sum()?, "sum of versions")?;
+            }
+        }
+        Ok(ver_sum)
+    }
+
+    /// Value of the packet applying the operations of its type to the sub-packets
+    ///
+    /// Fails if a sum or product doesn't fit in `C`, `get_val::<BigUint>` never does with
+    /// the `bigint` feature.
+    pub fn get_val<C: Count>(&self) -> Result<C> {
+        let val = match &self.payload {
+            Payload::Literal(val) => C::from_u64(*val),
+            Payload::PacketList(pl) => {
+                let vals = pl.iter().map(|p| p.get_val()).collect::<Result<Vec<C>>>()?;
+                match self.type_id {
+                    0 => {
+                        let mut sum = C::zero();
+                        for val in &vals {
+                            add_to(&mut sum, val, "sum of packets")?;
+                        }
+                        sum
+                    }
+                    1 => {
+                        let mut product = C::from_u64(1);
+                        for val in &vals {
+                            product = product
+                                .try_mul(val)
+                                .ok_or_else(|| overflow("product of packets"))?;
+                        }
+                        product
+                    }
+                    2 => vals.into_iter().min().expect("operators have sub-packets"),
+                    3 => vals.into_iter().max().expect("operators have sub-packets"),
+                    5 if vals[0] > vals[1] => C::from_u64(1),
+                    6 if vals[0] < vals[1] => C::from_u64(1),
+                    7 if vals[0] == vals[1] => C::from_u64(1),
+                    _ => C::zero(),
+                }
+            }
+        };
+        Ok(val)
+    }
+}
+
+/// Contents of a packet
+#[derive(Debug)]
+pub enum Payload {
+    /// Value of a literal packet, it must fit in 64 bits
+    Literal(u64),
+    /// Sub-packets of an operator, at least one and exactly two for the comparisons
+    PacketList(Vec<Packet>),
+}
+
+/// Most operators a packet can be nested in, bounds the stack used to read it
+pub const MAX_NESTING: usize = 256;
+
+/// Reader of the transmission keeping count of the bits read, to locate errors
+struct Bits<'a, I> {
+    reader: &'a mut I,
+    pos: u64,
+    /// Operators open at the current bit
+    depth: usize,
+}
+
+impl<I: BitRead> Bits<'_, I> {
+    fn read(&mut self, bits: u32) -> ParseResult<u64> {
+        let val = self.reader.read(bits).map_err(|why| self.error(why))?;
+        self.pos += u64::from(bits);
+        Ok(val)
+    }
+
+    fn read_bit(&mut self) -> ParseResult<bool> {
+        let bit = self.reader.read_bit().map_err(|why| self.error(why))?;
+        self.pos += 1;
+        Ok(bit)
+    }
+
+    fn error(&self, why: io::Error) -> ParseError {
+        match why.kind() {
+            io::ErrorKind::UnexpectedEof => ParseError::Truncated(self.pos),
+            _ => ParseError::Io(why),
+        }
+    }
+}
+
+/// Reads one packet and its sub-packets
+///
+/// Fails if the transmission ends before the packet does, or if the packet is malformed.
+pub fn read_packet<I: BitRead>(reader: &mut I) -> ParseResult<Packet> {
+    packet(&mut Bits {
+        reader,
+        pos: 0,
+        depth: 0,
+    })
+}
+
+fn packet<I: BitRead>(bits: &mut Bits<I>) -> ParseResult<Packet> {
+    let start = bits.pos;
+    let version = bits.read(3)?;
+    let type_id = bits.read(3)?;
+    let payload = match type_id {
+        4 => read_literal(bits, start)?,
+        _ => read_packet_list(bits, start, type_id)?,
+    };
+
+    Ok(Packet {
+        version,
+        type_id,
+        size: bits.pos - start,
+        payload,
+    })
+}
+
+/// Literal of the packet starting at bit `start`
+fn read_literal<I: BitRead>(bits: &mut Bits<I>, start: u64) -> ParseResult<Payload> {
+    let mut more_nibbles = true;
+    let mut literal: u64 = 0;
+
+    while more_nibbles {
+        more_nibbles = bits.read_bit()?;
+        let nibble = bits.read(4)?;
+        if literal >> 60 != 0 {
+            return Err(ParseError::LiteralTooLarge(start));
+        }
+        literal <<= 4;
+        literal += nibble;
+    }
+
+    Ok(Payload::Literal(literal))
+}
+
+/// Sub-packets of the operator of type `type_id` starting at bit `start`
+fn read_packet_list<I: BitRead>(
+    bits: &mut Bits<I>,
+    start: u64,
+    type_id: u64,
+) -> ParseResult<Payload> {
+    if bits.depth == MAX_NESTING {
+        return Err(ParseError::TooDeep(start));
+    }
+    bits.depth += 1;
+    let is_mode_number_of_subpackets = bits.read_bit()?;
+    let mut packet_list = vec![];
+
+    if is_mode_number_of_subpackets {
+        let packets_in_payload = bits.read(11)?;
+        for _ in 0..packets_in_payload {
+            packet_list.push(packet(bits)?);
+        }
+    } else {
+        let length_field = bits.read(15)?;
+        let end = bits.pos + length_field;
+        while bits.pos < end {
+            packet_list.push(packet(bits)?);
+        }
+        if bits.pos != end {
+            return Err(ParseError::LengthMismatch(start));
+        }
+    }
+    bits.depth -= 1;
+
+    let count = packet_list.len();
+    if count == 0 || ((5..=7).contains(&type_id) && count != 2) {
+        return Err(ParseError::Operands {
+            bit: start,
+            type_id,
+            count,
+        });
+    }
+    Ok(Payload::PacketList(packet_list))
+}
+
+/// Decodes the outermost packet of the transmission
+pub fn decode(byte_stream: &[u8]) -> Result<Packet> {
+    let mut cursor = Cursor::new(byte_stream);
+    let mut reader = BitReader::endian(&mut cursor, BigEndian);
+
+    Ok(read_packet(&mut reader)?)
+}
+
+/// Solver of the day, part 1 sums the versions and part 2 evaluates the expression
+pub struct Day16;
+
+impl Solver for Day16 {
+    const DAY: u32 = 16;
+
+    type Input = Vec<u8>;
+
+    fn parse<R: BufRead>(&self, mut reader: R) -> Result<Vec<u8>> {
+        let mut input = String::new();
+        reader.read_to_string(&mut input)?;
+        Ok(parse_input(&input)?)
+    }
+
+    fn part1(&self, byte_stream: &Vec<u8>) -> Result<Answer> {
+        Ok(decode(byte_stream)?.get_version_sum()?.into())
+    }
+
+    fn part2(&self, byte_stream: &Vec<u8>) -> Result<Answer> {
+        Ok(decode(byte_stream)?.get_val::<u64>()?.into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use aoc_common::Error;
+
+    const F1: &str = "D2FE28";
+    const F2: &str = "38006F45291200";
+    const F3: &str = "EE00D40C823060";
+    const F4: &str = "8A004A801A8002F478";
+    const F5: &str = "620080001611562C8802118E34";
+    const F6: &str = "C0015000016115A2E0802F182340";
+    const F7: &str = "A0016C880162017C3686B18A3D4780";
+
+    const S1: &str = "C200B40A82";
+    const S2: &str = "04005AC33890";
+    const S3: &str = "880086C3E88112";
+    const S4: &str = "CE00C43D881120";
+    const S5: &str = "D8005AC2A8F0";
+    const S6: &str = "F600BC2D8F";
+    const S7: &str = "9C005AC2F8F0";
+    const S8: &str = "9C0141080250320F1802104A08";
+
+    #[test]
+    fn test_get_val() {
+        let byte_stream = parse_input(S1).unwrap();
+        let mut cursor = Cursor::new(&byte_stream);
+        let mut reader = BitReader::endian(&mut cursor, BigEndian);
+        let packet = read_packet(&mut reader).unwrap();
+
+        assert_eq!(3, packet.get_val::<u64>().unwrap());
+
+        let byte_stream = parse_input(S2).unwrap();
+        let mut cursor = Cursor::new(&byte_stream);
+        let mut reader = BitReader::endian(&mut cursor, BigEndian);
+        let packet = read_packet(&mut reader).unwrap();
+
+        assert_eq!(54, packet.get_val::<u64>().unwrap());
+
+        let byte_stream = parse_input(S3).unwrap();
+        let mut cursor = Cursor::new(&byte_stream);
+        let mut reader = BitReader::endian(&mut cursor, BigEndian);
+        let packet = read_packet(&mut reader).unwrap();
+
+        assert_eq!(7, packet.get_val::<u64>().unwrap());
+
+        let byte_stream = parse_input(S4).unwrap();
+        let mut cursor = Cursor::new(&byte_stream);
+        let mut reader = BitReader::endian(&mut cursor, BigEndian);
+        let packet = read_packet(&mut reader).unwrap();
+
+        assert_eq!(9, packet.get_val::<u64>().unwrap());
+
+        let byte_stream = parse_input(S5).unwrap();
+        let mut cursor = Cursor::new(&byte_stream);
+        let mut reader = BitReader::endian(&mut cursor, BigEndian);
+        let packet = read_packet(&mut reader).unwrap();
+
+        assert_eq!(1, packet.get_val::<u64>().unwrap());
+
+        let byte_stream = parse_input(S6).unwrap();
+        let mut cursor = Cursor::new(&byte_stream);
+        let mut reader = BitReader::endian(&mut cursor, BigEndian);
+        let packet = read_packet(&mut reader).unwrap();
+
+        assert_eq!(0, packet.get_val::<u64>().unwrap());
+
+        let byte_stream = parse_input(S7).unwrap();
+        let mut cursor = Cursor::new(&byte_stream);
+        let mut reader = BitReader::endian(&mut cursor, BigEndian);
+        let packet = read_packet(&mut reader).unwrap();
+
+        assert_eq!(0, packet.get_val::<u64>().unwrap());
+
+        let byte_stream = parse_input(S8).unwrap();
+        let mut cursor = Cursor::new(&byte_stream);
+        let mut reader = BitReader::endian(&mut cursor, BigEndian);
+        let packet = read_packet(&mut reader).unwrap();
+
+        assert_eq!(1, packet.get_val::<u64>().unwrap());
+    }
+
+    #[test]
+    fn test_version_sum() {
+        let byte_stream = parse_input(F1).unwrap();
+        let mut cursor = Cursor::new(&byte_stream);
+        let mut reader = BitReader::endian(&mut cursor, BigEndian);
+        let packet = read_packet(&mut reader).unwrap();
+
+        assert_eq!(6, packet.get_version_sum().unwrap());
+
+        let byte_stream = parse_input(F2).unwrap();
+        let mut cursor = Cursor::new(&byte_stream);
+        let mut reader = BitReader::endian(&mut cursor, BigEndian);
+        let packet = read_packet(&mut reader).unwrap();
+
+        assert_eq!(9, packet.get_version_sum().unwrap());
+
+        let byte_stream = parse_input(F3).unwrap();
+        let mut cursor = Cursor::new(&byte_stream);
+        let mut reader = BitReader::endian(&mut cursor, BigEndian);
+        let packet = read_packet(&mut reader).unwrap();
+
+        assert_eq!(14, packet.get_version_sum().unwrap());
+
+        let byte_stream = parse_input(F4).unwrap();
+        let mut cursor = Cursor::new(&byte_stream);
+        let mut reader = BitReader::endian(&mut cursor, BigEndian);
+        let packet = read_packet(&mut reader).unwrap();
+
+        assert_eq!(16, packet.get_version_sum().unwrap());
+
+        let byte_stream = parse_input(F5).unwrap();
+        let mut cursor = Cursor::new(&byte_stream);
+        let mut reader = BitReader::endian(&mut cursor, BigEndian);
+        let packet = read_packet(&mut reader).unwrap();
+
+        assert_eq!(12, packet.get_version_sum().unwrap());
+
+        let byte_stream = parse_input(F6).unwrap();
+        let mut cursor = Cursor::new(&byte_stream);
+        let mut reader = BitReader::endian(&mut cursor, BigEndian);
+        let packet = read_packet(&mut reader).unwrap();
+
+        assert_eq!(23, packet.get_version_sum().unwrap());
+
+        let byte_stream = parse_input(F7).unwrap();
+        let mut cursor = Cursor::new(&byte_stream);
+        let mut reader = BitReader::endian(&mut cursor, BigEndian);
+        let packet = read_packet(&mut reader).unwrap();
+
+        assert_eq!(31, packet.get_version_sum().unwrap());
+    }
+
+    #[test]
+    fn test_read_packet_with_packet_list_packetcounttype() {
+        let byte_stream = parse_input(F3).unwrap();
+
+        let mut cursor = Cursor::new(&byte_stream);
+        let mut reader = BitReader::endian(&mut cursor, BigEndian);
+
+        let packet = read_packet(&mut reader).unwrap();
+        assert_eq!(7, packet.version);
+        assert_eq!(3, packet.type_id);
+        assert_eq!(14 * 4 - 5, packet.size());
+        let plist = match packet.payload {
+            Payload::PacketList(plist) => plist,
+            _ => vec![],
+        };
+        assert_eq!(3, plist.len());
+
+        let packet = &plist[0];
+        assert_eq!(2, packet.version);
+        assert_eq!(4, packet.type_id);
+        assert_eq!(11, packet.size);
+        let literal = match packet.payload {
+            Payload::Literal(literal) => literal,
+            _ => 0,
+        };
+        assert_eq!(1, literal);
+
+        let packet = &plist[1];
+        assert_eq!(4, packet.version);
+        assert_eq!(4, packet.type_id);
+        assert_eq!(11, packet.size);
+        let literal = match packet.payload {
+            Payload::Literal(literal) => literal,
+            _ => 0,
+        };
+        assert_eq!(2, literal);
+
+        let packet = &plist[2];
+        assert_eq!(1, packet.version);
+        assert_eq!(4, packet.type_id);
+        assert_eq!(11, packet.size);
+        let literal = match packet.payload {
+            Payload::Literal(literal) => literal,
+            _ => 0,
+        };
+        assert_eq!(3, literal);
+    }
+
+    #[test]
+    fn test_read_packet_with_packet_list_lengthtype() {
+        let byte_stream = parse_input(F2).unwrap();
+
+        let mut cursor = Cursor::new(&byte_stream);
+        let mut reader = BitReader::endian(&mut cursor, BigEndian);
+
+        let packet = read_packet(&mut reader).unwrap();
+        assert_eq!(1, packet.version);
+        assert_eq!(6, packet.type_id);
+        assert_eq!(14 * 4 - 7, packet.size);
+        assert_eq!(14 * 4 - 7, packet.size());
+        let plist = match packet.payload {
+            Payload::PacketList(plist) => plist,
+            _ => vec![],
+        };
+        assert_eq!(2, plist.len());
+
+        let packet = &plist[0];
+        assert_eq!(6, packet.version);
+        assert_eq!(4, packet.type_id);
+        assert_eq!(11, packet.size);
+        let literal = match packet.payload {
+            Payload::Literal(literal) => literal,
+            _ => 0,
+        };
+        assert_eq!(10, literal);
+
+        let packet = &plist[1];
+        assert_eq!(2, packet.version);
+        assert_eq!(4, packet.type_id);
+        assert_eq!(16, packet.size);
+        let literal = match packet.payload {
+            Payload::Literal(literal) => literal,
+            _ => 0,
+        };
+        assert_eq!(20, literal)
+    }
+
+    #[test]
+    fn test_read_packet_with_literal() {
+        let byte_stream = parse_input(F1).unwrap();
+
+        let mut cursor = Cursor::new(&byte_stream);
+        let mut reader = BitReader::endian(&mut cursor, BigEndian);
+
+        let packet = read_packet(&mut reader).unwrap();
+        assert_eq!(6, packet.version);
+        assert_eq!(4, packet.type_id);
+        assert_eq!(21, packet.size());
+        let literal = match packet.payload {
+            Payload::Literal(literal) => literal,
+            _ => 0,
+        };
+        assert_eq!(2021, literal)
+    }
+
+    #[test]
+    fn test_bitstream_input() {
+        let byte_stream = parse_input(F1).unwrap();
+
+        let mut cursor = Cursor::new(&byte_stream);
+        let mut reader = BitReader::endian(&mut cursor, BigEndian);
+
+        match reader.read::<u8>(3) {
+            Ok(version) => assert_eq!(6, version),
+            Err(msg) => panic!("Should not return error: {}", msg),
+        }
+
+        match reader.read::<u8>(3) {
+            Ok(type_id) => assert_eq!(4, type_id),
+            Err(msg) => panic!("Should not return error: {}", msg),
+        }
+
+        for v in [7, 14] {
+            match reader.read_bit() {
+                Ok(is_not_last) => assert!(is_not_last),
+                Err(msg) => panic!("Should not return error: {}", msg),
+            }
+
+            match reader.read::<u8>(4) {
+                Ok(b) => assert_eq!(v, b),
+                Err(msg) => panic!("Should not return error: {}", msg),
+            }
+        }
+        match reader.read_bit() {
+            Ok(is_not_last) => assert!(!is_not_last),
+            Err(msg) => panic!("Should not return error: {}", msg),
+        }
+
+        match reader.read::<u8>(4) {
+            Ok(b) => assert_eq!(5, b),
+            Err(msg) => panic!("Should not return error: {}", msg),
+        }
+    }
+
+    #[test]
+    fn test_parse_input_errors() {
+        match parse_input("D2FE2G") {
+            Err(ParseError::InvalidHexDigit(loc)) => assert_eq!(Location::new(1, 6, "G"), loc),
+            _ => panic!("expected an invalid hexadecimal digit"),
+        }
+
+        match parse_input("D2FE2\n") {
+            Err(ParseError::OddLength(loc)) => assert_eq!(Location::new(1, 5, "2"), loc),
+            _ => panic!("expected an odd length"),
+        }
+    }
+
+    /// Transmission of the bits in `text`, padded with zeros to a whole number of bytes
+    fn bits(text: &str) -> Vec<u8> {
+        let mut bytes = vec![0; text.len().div_ceil(8)];
+        for (i, c) in text.chars().enumerate() {
+            if c == '1' {
+                bytes[i / 8] |= 0x80 >> (i % 8);
+            }
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_read_packet_errors() {
+        let truncated = |bytes: &[u8]| {
+            let mut cursor = Cursor::new(bytes);
+            match read_packet(&mut BitReader::endian(&mut cursor, BigEndian)) {
+                Err(ParseError::Truncated(bit)) => bit,
+                other => panic!("expected a truncated transmission, got {:?}", other),
+            }
+        };
+        assert_eq!(0, truncated(&[]));
+        assert_eq!(7, truncated(&[0xD2]));
+        assert_eq!(7, truncated(&[0xC0]));
+        // Operator of 2 sub-packets followed by a single one, the padding holds a version
+        assert_eq!(32, truncated(&bits("000000100000000010000100000001")));
+
+        // Literal of 17 nibbles, embedded in an operator at bit 0
+        let text = format!("000000100000000001000100{}00000", "11111".repeat(16));
+        let mut cursor = Cursor::new(bits(&text));
+        match read_packet(&mut BitReader::endian(&mut cursor, BigEndian)) {
+            Err(ParseError::LiteralTooLarge(bit)) => assert_eq!(18, bit),
+            other => panic!("expected a too large literal, got {:?}", other),
+        }
+
+        // Operator of 10 bits holding a literal of 11 bits
+        let mut cursor = Cursor::new(bits("0000000000000000001010000100000001"));
+        match read_packet(&mut BitReader::endian(&mut cursor, BigEndian)) {
+            Err(ParseError::LengthMismatch(bit)) => assert_eq!(0, bit),
+            other => panic!("expected a length mismatch, got {:?}", other),
+        }
+
+        let err = decode(&[0xD2]).unwrap_err();
+        assert_eq!("transmission truncated at bit 7", err.to_string());
+    }
+
+    fn literal(val: u64) -> Packet {
+        Packet {
+            version: 0,
+            type_id: 4,
+            size: 0,
+            payload: Payload::Literal(val),
+        }
+    }
+
+    #[test]
+    fn test_get_val_overflow() {
+        let product = Packet {
+            version: 0,
+            type_id: 1,
+            size: 0,
+            payload: Payload::PacketList(vec![literal(u64::MAX), literal(2)]),
+        };
+        match product.get_val::<u64>() {
+            Err(Error::Overflow(_)) => (),
+            _ => panic!("expected an overflow"),
+        }
+
+        // 16 groups fit in 64 bits, 18 don't
+        let literal = parse_input("D2318C6318C6318C6318BC").unwrap();
+        assert_eq!(
+            0x1111_1111_1111_111F,
+            decode(&literal).unwrap().get_val::<u64>().unwrap()
+        );
+        let literal = parse_input("D3FFFFFFFFFFFFFFFFFFFFEF").unwrap();
+        assert!(decode(&literal).is_err());
+    }
+
+    #[test]
+    fn test_read_packet_operands() {
+        // operator of `count` literals 1, counting its sub-packets
+        let operator = |type_id: u64, count: usize| {
+            let text = format!(
+                "000{:03b}1{:011b}{}",
+                type_id,
+                count,
+                "00010000001".repeat(count)
+            );
+            let mut cursor = Cursor::new(bits(&text));
+            read_packet(&mut BitReader::endian(&mut cursor, BigEndian))
+        };
+        for (type_id, count) in [(0, 0), (1, 0), (2, 0), (3, 0), (5, 1), (6, 3), (7, 0)] {
+            match operator(type_id, count) {
+                Err(ParseError::Operands {
+                    bit: 0,
+                    type_id: t,
+                    count: n,
+                }) => assert_eq!((type_id, count), (t, n)),
+                other => panic!("expected an operand error, got {:?}", other),
+            }
+        }
+        for (type_id, count) in [(0, 1), (2, 3), (5, 2), (7, 2)] {
+            assert!(operator(type_id, count).is_ok());
+        }
+
+        let err = decode(&[0x0A, 0x00, 0x00]).unwrap_err();
+        assert_eq!(
+            "bit 0: operator of type 2 has 0 sub-packets, expected at least 1",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn test_read_packet_too_deep() {
+        // minimums of a single sub-packet nested `depth` times around a literal
+        let nested = |depth| {
+            let text = "000010100000000001".repeat(depth) + "00010000001";
+            let mut cursor = Cursor::new(bits(&text));
+            read_packet(&mut BitReader::endian(&mut cursor, BigEndian))
+        };
+        let packet = nested(MAX_NESTING).unwrap();
+        assert_eq!(1, packet.get_val::<u64>().unwrap());
+        match nested(MAX_NESTING + 1) {
+            Err(ParseError::TooDeep(bit)) => assert_eq!(18 * MAX_NESTING as u64, bit),
+            other => panic!("expected a packet too deep, got {:?}", other),
+        }
+        // far deeper than the stack could take
+        assert!(nested(200_000).is_err());
+    }
+
+    #[cfg(feature = "bigint")]
+    #[test]
+    fn test_get_val_bigint() {
+        use aoc_common::BigUint;
+
+        let product = Packet {
+            version: 0,
+            type_id: 1,
+            size: 0,
+            payload: Payload::PacketList(vec![literal(u64::MAX), literal(2)]),
+        };
+        let expected = BigUint::from(u64::MAX) * 2_u32;
+        assert_eq!(expected, product.get_val::<BigUint>().unwrap());
+    }
+}
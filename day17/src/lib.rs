@@ -0,0 +1,292 @@
+//! Day 17: Trick Shot, finds the launch velocities of the probe that reach the target area
+
+use aoc_common::{Answer, Location, Result, Solver};
+use std::io::BufRead;
+
+aoc_common::parse_error! {
+    /// Errors found parsing the target area
+    pub enum ParseError {
+        /// A line that is not like "target area: x=20..30, y=-10..-5"
+        InvalidTarget(Location),
+        /// A bound of the target area that is not an integer
+        InvalidBound(Location),
+    }
+
+    fn fmt(&self, f) {
+        ParseError::InvalidTarget(loc) => write!(f, "{}: invalid target '{}'", loc, loc.token),
+        ParseError::InvalidBound(loc) => write!(f, "{}: invalid bound '{}'", loc, loc.token),
+    }
+}
+
+type ParseResult<T> = std::result::Result<T, ParseError>;
+
+/// Target area, the bounds are inclusive
+#[derive(Debug)]
+pub struct Target {
+    x_min: i64,
+    x_max: i64,
+    y_min: i64,
+    y_max: i64,
+}
+
+impl Target {
+    pub fn new(x_min: i64, x_max: i64, y_min: i64, y_max: i64) -> Target {
+        Target{ x_min,
+                x_max,
+                y_min,
+                y_max }
+    }
+
+    /// Whether the probe is inside the target area
+    pub fn is_inside(&self, state: &State) -> bool {
+        (state.x >= self.x_min && state.x <= self.x_max) && (state.y >= self.y_min && state.y <= self.y_max)
+    }
+}
+
+/// Position and velocity of the probe
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct State {
+    pub x: i64,
+    pub y: i64,
+    pub vx: i64,
+    pub vy: i64,
+}
+
+impl State {
+    pub fn new(x: i64, y: i64, vx: i64, vy: i64) -> State {
+        State {
+            x,
+            y,
+            vx,
+            vy,
+        }
+    }
+
+    /// State after one step, drag slows x down and gravity pulls y down
+    pub fn step(&self) -> State {
+        let new_vx = if self.vx.abs() > 0 { self.vx -self.vx.signum() } else { 0_i64 };
+
+        State{
+            x: self.x + self.vx,
+            y: self.y + self.vy,
+            vx: new_vx,
+            vy: self.vy - 1,
+        }
+    }
+}
+
+/// States of the probe from the launch
+pub type Trajectory = Vec<State>;
+
+/// Launches the probe from the origin until it has passed the target area
+pub fn make_trajectory(vx: i64, vy: i64, target: &Target) -> Trajectory {
+    let mut vec = Trajectory::new();
+    let mut state = State::new(0, 0, vx, vy);
+    vec.push(state);
+    loop {
+        if (state.x > target.x_max)
+            || (state.x < target.x_min && state.vx == 0)
+            || (state.y < target.y_min)
+        {
+            break
+        }
+        state = vec.last().unwrap().step();
+        vec.push(state);
+    }
+    vec
+}
+
+/// Whether any state of the trajectory is inside the target area
+pub fn is_trajectory_in_target(traj: &Trajectory, target: &Target) -> bool {
+    for state in traj {
+        if target.is_inside(state) {
+            return true;
+        }
+    }
+    false
+}
+
+/// Highest y reached by a trajectory hitting the target area
+pub fn find_highest_shooting_position(target: &Target) -> i64 {
+    let max_vx = ((target.x_max.pow(2) + target.y_min.pow(2)) as f64).sqrt() as i64;
+    let min_vy = -((target.x_min.pow(2) + target.y_min.pow(2)) as f64).sqrt() as i64;
+    let mut y_max: i64 = 0;
+    for vx in 1..=max_vx {
+        for vy in min_vy..-target.y_min {
+            let traj = make_trajectory(vx, vy, target);
+            if is_trajectory_in_target(&traj, target) {
+                let tmp_y_max = traj.iter().map(|s| s.y).max().unwrap();
+                if tmp_y_max >= y_max {
+                    y_max = tmp_y_max;
+                }
+            }
+        }
+    }
+    y_max
+}
+
+
+/// Number of launch velocities hitting the target area
+pub fn count_good_shooting_position(target: &Target) -> u64 {
+    let max_vx = ((target.x_max.pow(2) + target.y_min.pow(2)) as f64).sqrt() as i64;
+    let min_vy = -((target.x_min.pow(2) + target.y_min.pow(2)) as f64).sqrt() as i64;
+    let mut counter: u64 = 0;
+    for vx in 1..=max_vx {
+        for vy in min_vy..-target.y_min {
+            let traj = make_trajectory(vx, vy, target);
+            if is_trajectory_in_target(&traj, target) {
+                counter += 1;
+            }
+        }
+    }
+    counter
+}
+
+/// Parses a target description like "target area: x=20..30, y=-10..-5"
+pub fn parse_target(line: &str) -> ParseResult<Target> {
+    let ranges = || -> Option<[&str; 4]> {
+        let (x_range, y_range) = line.trim().strip_prefix("target area: ")?.split_once(", ")?;
+        let (x_min, x_max) = x_range.strip_prefix("x=")?.split_once("..")?;
+        let (y_min, y_max) = y_range.strip_prefix("y=")?.split_once("..")?;
+        Some([x_min, x_max, y_min, y_max])
+    };
+    let tokens = match ranges() {
+        Some(tokens) => tokens,
+        None => return Err(ParseError::InvalidTarget(Location::in_line(1, line, line.trim()))),
+    };
+
+    let mut bounds = [0; 4];
+    for (bound, token) in bounds.iter_mut().zip(tokens) {
+        *bound = match token.parse() {
+            Ok(val) => val,
+            Err(_) => return Err(ParseError::InvalidBound(Location::in_line(1, line, token))),
+        };
+    }
+
+    let [x_min, x_max, y_min, y_max] = bounds;
+    Ok(Target::new(x_min, x_max, y_min, y_max))
+}
+
+/// Solver of the day, part 1 finds the highest position reached and part 2 counts the
+/// velocities hitting the target
+pub struct Day17;
+
+impl Solver for Day17 {
+    const DAY: u32 = 17;
+
+    type Input = Target;
+
+    fn parse<R: BufRead>(&self, mut reader: R) -> Result<Target> {
+        let mut line = String::new();
+        reader.read_line(&mut line)?;
+        Ok(parse_target(&line)?)
+    }
+
+    fn part1(&self, target: &Target) -> Result<Answer> {
+        Ok(find_highest_shooting_position(target).into())
+    }
+
+    fn part2(&self, target: &Target) -> Result<Answer> {
+        Ok(count_good_shooting_position(target).into())
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+
+
+
+    #[test]
+    fn test_parse_target() {
+        let target = parse_target("target area: x=20..30, y=-10..-5\n").unwrap();
+        assert_eq!(
+            (20, 30, -10, -5),
+            (target.x_min, target.x_max, target.y_min, target.y_max)
+        );
+
+        match parse_target("target area: x=20..30") {
+            Err(ParseError::InvalidTarget(loc)) => assert_eq!(1, loc.column),
+            _ => panic!("expected an invalid target"),
+        }
+        assert!(parse_target("x=20..30, y=-10..-5").is_err());
+        match parse_target("target area: x=20..30, y=-1O..-5") {
+            Err(ParseError::InvalidBound(loc)) => assert_eq!(Location::new(1, 26, "-1O"), loc),
+            _ => panic!("expected an invalid bound"),
+        }
+    }
+
+    #[test]
+    fn test_count_good_shooting_position() {
+        let target = Target::new(20, 30, -10, -5);
+        let counter = count_good_shooting_position(&target);
+        assert_eq!(112, counter);
+    }
+
+
+    #[test]
+    fn test_find_highest_shooting_position() {
+        let target = Target::new(20, 30, -10, -5);
+        let y_max = find_highest_shooting_position(&target);
+        assert_eq!(45, y_max);
+    }
+
+    #[test]
+    fn test_is_trajectory_in_target() {
+        let target = Target::new(20, 30, -10, -5);
+
+        let traj = make_trajectory(7, 2, &target);
+        assert!(is_trajectory_in_target(&traj, &target));
+
+        let traj = make_trajectory(6, 3, &target);
+        assert!(is_trajectory_in_target(&traj, &target));
+
+        let traj = make_trajectory(9, 0, &target);
+        assert!(is_trajectory_in_target(&traj, &target));
+
+        let traj = make_trajectory(17, 4, &target);
+        assert!(!is_trajectory_in_target(&traj, &target));
+
+    }
+
+    #[test]
+    fn test_make_trajectory() {
+        let target = Target::new(211, 232, -124, -69);
+        let traj = make_trajectory(200, 0, &target);
+
+        assert_eq!(3, traj.len());
+        assert_eq!(Some(&State::new(0, 0, 200, 0)), traj.first());
+        assert_eq!(Some(&State::new(200, 0, 199, -1)), traj.get(1));
+        assert_eq!(Some(&State::new(399, -1, 198, -2)), traj.get(2));
+
+        let traj = make_trajectory(0, -10, &target);
+        assert_eq!(1, traj.len());
+        assert_eq!(Some(&State::new(0, 0, 0, -10)), traj.first());
+
+        let traj = make_trajectory(10, -100, &target);
+        assert_eq!(3, traj.len());
+        assert_eq!(Some(&State::new(0, 0, 10, -100)), traj.first());
+        assert_eq!(Some(&State::new(10, -100, 9, -101)), traj.get(1));
+        assert_eq!(Some(&State::new(19, -201, 8, -102)), traj.get(2));
+
+    }
+
+    #[test]
+    fn test_state_step() {
+        let state = State::new(0, 0, 0, 0);
+        let mut new_state = state.step();
+
+        assert_eq!(State::new(0, 0, 0, -1), new_state);
+        new_state = new_state.step();
+        assert_eq!(State::new(0, -1, 0, -2), new_state);
+        new_state = new_state.step();
+        assert_eq!(State::new(0, -3, 0, -3), new_state);
+
+        let state = State::new(10, 0, 5, 0);
+        let mut new_state = state.step();
+        assert_eq!(State::new(15, 0, 4, -1), new_state);
+        new_state = new_state.step();
+        assert_eq!(State::new(19, -1, 3, -2), new_state);
+    }
+}